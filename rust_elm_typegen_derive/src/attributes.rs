@@ -0,0 +1,281 @@
+//! Checks the `#[elm(...)]` and `#[serde(...)]` attributes at compile time,
+//! with the same rules `ElmDefinitions::add_item` applies at runtime.
+
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Lit, Meta, NestedMeta};
+
+use crate::{is_conversion, is_skipped, serde_metas};
+
+const RENAME_RULES: [&str; 8] = [
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+pub fn validate(input: &DeriveInput) -> syn::Result<()> {
+    let elm = ElmAttributes::parse(&input.attrs)?;
+    check_serde(&input.attrs)?;
+    check_conversion(&input.attrs)?;
+    match &input.data {
+        Data::Struct(data) => {
+            if elm.integer {
+                let message = "#[elm(integer)] only applies to enums";
+                return Err(syn::Error::new_spanned(&input.ident, message));
+            }
+            check_fields(&data.fields)?;
+            if has_path(&input.attrs, "transparent")? {
+                let mut fields = 0;
+                for field in &data.fields {
+                    if !is_skipped(field)? {
+                        fields += 1;
+                    }
+                }
+                if fields != 1 {
+                    let message =
+                        "A transparent struct needs exactly one field that is not skipped";
+                    return Err(syn::Error::new_spanned(&input.ident, message));
+                }
+            }
+        }
+        Data::Enum(data) => {
+            let untagged = has_path(&input.attrs, "untagged")?;
+            let internal =
+                has_name_value(&input.attrs, "tag")? && !has_name_value(&input.attrs, "content")?;
+            for variant in &data.variants {
+                no_elm_attributes(&variant.attrs)?;
+                check_serde(&variant.attrs)?;
+                check_fields(&variant.fields)?;
+                let is_unit = variant.fields.is_empty();
+                let other = has_path(&variant.attrs, "other")?;
+                let reason = if elm.integer && !is_unit {
+                    "enums with an integer representation can only have unit variants"
+                } else if other && untagged {
+                    "untagged enums can't have an other variant"
+                } else if other && !is_unit {
+                    "only unit variants can be the other variant"
+                } else if internal
+                    && matches!(&variant.fields, Fields::Unnamed(f) if f.unnamed.len() > 1)
+                {
+                    "internally tagged enums can't contain tuple variants"
+                } else {
+                    continue;
+                };
+                let message = format!(
+                    "Variant {} of {} can't be serialized: {}",
+                    variant.ident, input.ident, reason
+                );
+                return Err(syn::Error::new_spanned(&variant.ident, message));
+            }
+        }
+        // Reported by `dependencies`.
+        Data::Union(_) => {}
+    }
+    Ok(())
+}
+
+/// Our own attributes, only on the type itself.
+#[derive(Default)]
+struct ElmAttributes {
+    integer: bool,
+}
+
+impl ElmAttributes {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<ElmAttributes> {
+        let mut result = ElmAttributes::default();
+        for attr in attrs {
+            if !attr.path.is_ident("elm") {
+                continue;
+            }
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(syn::Error::new_spanned(meta, "Expected #[elm(...)]")),
+            };
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("opaque") => {}
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("integer") => {
+                        result.integer = true;
+                    }
+                    other => return Err(syn::Error::new_spanned(other, "Unknown elm attribute")),
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn no_elm_attributes(attrs: &[syn::Attribute]) -> syn::Result<()> {
+    match attrs.iter().find(|attr| attr.path.is_ident("elm")) {
+        Some(attr) => {
+            let message = "elm attributes only apply to the type itself";
+            Err(syn::Error::new_spanned(attr, message))
+        }
+        None => Ok(()),
+    }
+}
+
+fn check_fields(fields: &Fields) -> syn::Result<()> {
+    for field in fields {
+        no_elm_attributes(&field.attrs)?;
+        check_serde(&field.attrs)?;
+    }
+    Ok(())
+}
+
+/// The values of the serde attributes that we read.
+fn check_serde(attrs: &[syn::Attribute]) -> syn::Result<()> {
+    for meta in serde_metas(attrs)? {
+        let name_value = match meta {
+            Meta::NameValue(name_value) => name_value,
+            _ => continue,
+        };
+        let path = &name_value.path;
+        let lit = &name_value.lit;
+        if ["rename", "tag", "content", "alias"]
+            .iter()
+            .any(|name| path.is_ident(name))
+        {
+            string_value(lit)?;
+        } else if path.is_ident("rename_all") || path.is_ident("rename_all_fields") {
+            let rule = string_value(lit)?;
+            if !RENAME_RULES.contains(&rule.as_str()) {
+                let message = format!("Unknown rename rule {:?}", rule);
+                return Err(syn::Error::new_spanned(lit, message));
+            }
+        } else if is_conversion(path) {
+            type_value(lit)?;
+        }
+    }
+    Ok(())
+}
+
+/// Elm only gets one type, so converting from and into different types is
+/// an error.
+fn check_conversion(attrs: &[syn::Attribute]) -> syn::Result<()> {
+    let mut into = None;
+    let mut from = None;
+    for meta in serde_metas(attrs)? {
+        if let Meta::NameValue(name_value) = meta {
+            if name_value.path.is_ident("into") {
+                into = Some(type_value(&name_value.lit)?);
+            } else if is_conversion(&name_value.path) {
+                from = Some(type_value(&name_value.lit)?);
+            }
+        }
+    }
+    match (into, from) {
+        (Some(into), Some(from)) if quote!(#into).to_string() != quote!(#from).to_string() => {
+            let message = "Converting from and into different types is not supported";
+            Err(syn::Error::new_spanned(into, message))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn has_path(attrs: &[syn::Attribute], name: &str) -> syn::Result<bool> {
+    Ok(serde_metas(attrs)?
+        .iter()
+        .any(|meta| matches!(meta, Meta::Path(path) if path.is_ident(name))))
+}
+
+fn has_name_value(attrs: &[syn::Attribute], name: &str) -> syn::Result<bool> {
+    Ok(serde_metas(attrs)?
+        .iter()
+        .any(|meta| matches!(meta, Meta::NameValue(name_value) if name_value.path.is_ident(name))))
+}
+
+fn string_value(lit: &Lit) -> syn::Result<String> {
+    match lit {
+        Lit::Str(lit_str) => Ok(lit_str.value()),
+        _ => Err(syn::Error::new_spanned(lit, "Expected a string")),
+    }
+}
+
+fn type_value(lit: &Lit) -> syn::Result<syn::Type> {
+    match lit {
+        Lit::Str(lit_str) => lit_str.parse(),
+        _ => Err(syn::Error::new_spanned(lit, "Expected a type in a string")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate;
+    use syn::{parse_quote, DeriveInput};
+
+    fn error(input: DeriveInput) -> String {
+        validate(&input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_valid_attributes() {
+        let input: DeriveInput = parse_quote! {
+            #[elm(opaque)]
+            #[serde(rename_all = "camelCase", into = "String", from = "String")]
+            struct UserId {
+                #[serde(rename = "value", skip_serializing_if = "Option::is_none")]
+                inner: Option<String>,
+            }
+        };
+        validate(&input).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_attributes() {
+        assert_eq!(
+            error(parse_quote! {
+                #[elm(transparent)]
+                struct UserId(u64);
+            }),
+            "Unknown elm attribute"
+        );
+        assert_eq!(
+            error(parse_quote! {
+                struct Login {
+                    #[elm(opaque)]
+                    user: String,
+                }
+            }),
+            "elm attributes only apply to the type itself"
+        );
+        assert_eq!(
+            error(parse_quote! {
+                #[serde(rename_all = "camel_case")]
+                struct Login {
+                    user: String,
+                }
+            }),
+            "Unknown rename rule \"camel_case\""
+        );
+        assert_eq!(
+            error(parse_quote! {
+                #[serde(into = "String", from = "u64")]
+                struct UserId(u64);
+            }),
+            "Converting from and into different types is not supported"
+        );
+        assert_eq!(
+            error(parse_quote! {
+                #[serde(transparent)]
+                struct Pair(u8, u8);
+            }),
+            "A transparent struct needs exactly one field that is not skipped"
+        );
+        assert_eq!(
+            error(parse_quote! {
+                #[elm(integer)]
+                enum Answer {
+                    No,
+                    Maybe(u8),
+                }
+            }),
+            "Variant Maybe of Answer can't be serialized: \
+             enums with an integer representation can only have unit variants"
+        );
+    }
+}
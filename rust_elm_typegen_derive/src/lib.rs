@@ -0,0 +1,236 @@
+//! `#[derive(ElmExport)]` marks a type for export to elm.
+//!
+//! The generated impl hands the source of the type to
+//! `ElmDefinitions::add_item`, which reads it like the source scanner does,
+//! and then collects the types of all fields. Field types are replaced by
+//! placeholders in that source and described by their own `ElmExport` impl,
+//! so aliases and hand-written impls are respected.
+
+mod attributes;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenTree;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, GenericArgument, Lit, LitStr, Meta,
+    NestedMeta, PathArguments, Type,
+};
+
+#[proc_macro_derive(ElmExport, attributes(elm))]
+pub fn derive_elm_export(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    attributes::validate(&input)?;
+    let name = &input.ident;
+    let dependencies = dependencies(&input)?;
+    let mut generics = input.generics.clone();
+    let type_parameters: Vec<_> = generics.type_params().map(|p| p.ident.clone()).collect();
+
+    let mut described = vec![];
+    let source = with_placeholders(input.clone(), &type_parameters, &mut described)?;
+    let source = quote!(#source).to_string();
+    let placeholders = (0..described.len()).map(placeholder).map(|p| p.to_string());
+    for parameter in &type_parameters {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#parameter: ::rust_elm_typegen::ElmExport));
+    }
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::rust_elm_typegen::ElmExport for #name #type_generics #where_clause {
+            fn elm_type() -> ::rust_elm_typegen::ElmType {
+                ::rust_elm_typegen::ElmType::NamedType(
+                    ::rust_elm_typegen::Identifier::new(stringify!(#name)),
+                    vec![#(<#type_parameters as ::rust_elm_typegen::ElmExport>::elm_type()),*],
+                )
+            }
+
+            fn collect_definitions(definitions: &mut ::rust_elm_typegen::ElmDefinitions) {
+                let described = vec![
+                    #((#placeholders, <#described as ::rust_elm_typegen::ElmExport>::elm_type())),*
+                ];
+                if definitions.add_item(module_path!(), #source, described) {
+                    #(<#dependencies as ::rust_elm_typegen::ElmExport>::collect_definitions(definitions);)*
+                }
+            }
+        }
+    })
+}
+
+fn placeholder(index: usize) -> syn::Ident {
+    format_ident!("__ElmType{}", index)
+}
+
+/// Replaces the target of `#[serde(into = "...")]` or else the types of all
+/// fields that are not skipped by placeholders. Their original types are
+/// pushed to `described`. `from` and `try_from` are left out, they don't
+/// change what is written and `attributes::validate` checked that they agree
+/// with `into`.
+fn with_placeholders(
+    mut input: DeriveInput,
+    type_parameters: &[syn::Ident],
+    described: &mut Vec<Type>,
+) -> syn::Result<DeriveInput> {
+    let mut converted = false;
+    for attr in &mut input.attrs {
+        if !attr.path.is_ident("serde") {
+            continue;
+        }
+        let mut metas = vec![];
+        for mut meta in serde_metas(std::slice::from_ref(attr))? {
+            if let Meta::NameValue(name_value) = &mut meta {
+                if name_value.path.is_ident("into") {
+                    if let Lit::Str(lit_str) = &name_value.lit {
+                        let mut ty = lit_str.parse()?;
+                        replace_types(&mut ty, type_parameters, described);
+                        let ty = quote!(#ty).to_string();
+                        name_value.lit = Lit::Str(LitStr::new(&ty, lit_str.span()));
+                        converted = true;
+                    }
+                } else if is_conversion(&name_value.path) {
+                    continue;
+                }
+            }
+            metas.push(meta);
+        }
+        *attr = parse_quote!(#[serde(#(#metas),*)]);
+    }
+    if converted {
+        return Ok(input);
+    }
+    let fields: Vec<&mut syn::Field> = match &mut input.data {
+        Data::Struct(data) => data.fields.iter_mut().collect(),
+        Data::Enum(data) => data
+            .variants
+            .iter_mut()
+            .flat_map(|v| &mut v.fields)
+            .collect(),
+        Data::Union(_) => vec![],
+    };
+    for field in fields {
+        if !is_skipped(field)? {
+            replace_types(&mut field.ty, type_parameters, described);
+        }
+    }
+    Ok(input)
+}
+
+/// Types that don't mention a type parameter are replaced as a whole. The
+/// others keep their outer type and only their arguments are replaced, so
+/// `Vec<(T, Id)>` becomes `Vec<(T, __ElmType0)>`.
+fn replace_types(ty: &mut Type, type_parameters: &[syn::Ident], described: &mut Vec<Type>) {
+    if !mentions_any(quote!(#ty), type_parameters) {
+        let placeholder = placeholder(described.len());
+        described.push(ty.clone());
+        *ty = parse_quote!(#placeholder);
+        return;
+    }
+    match ty {
+        Type::Path(type_path) => {
+            for segment in &mut type_path.path.segments {
+                if let PathArguments::AngleBracketed(arguments) = &mut segment.arguments {
+                    for argument in &mut arguments.args {
+                        if let GenericArgument::Type(ty) = argument {
+                            replace_types(ty, type_parameters, described);
+                        }
+                    }
+                }
+            }
+        }
+        Type::Tuple(tuple) => {
+            for element in &mut tuple.elems {
+                replace_types(element, type_parameters, described);
+            }
+        }
+        Type::Array(array) => replace_types(&mut array.elem, type_parameters, described),
+        Type::Slice(slice) => replace_types(&mut slice.elem, type_parameters, described),
+        Type::Reference(reference) => {
+            replace_types(&mut reference.elem, type_parameters, described)
+        }
+        Type::Paren(paren) => replace_types(&mut paren.elem, type_parameters, described),
+        Type::Group(group) => replace_types(&mut group.elem, type_parameters, described),
+        _ => {}
+    }
+}
+
+fn mentions_any(tokens: proc_macro2::TokenStream, idents: &[syn::Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => idents.contains(&ident),
+        TokenTree::Group(group) => mentions_any(group.stream(), idents),
+        _ => false,
+    })
+}
+
+fn is_conversion(path: &syn::Path) -> bool {
+    ["into", "from", "try_from"]
+        .iter()
+        .any(|attribute| path.is_ident(attribute))
+}
+
+/// The types that end up in the JSON: the target of `#[serde(into = "...")]`,
+/// or else the types of all fields that are not skipped.
+fn dependencies(input: &DeriveInput) -> syn::Result<Vec<syn::Type>> {
+    for meta in serde_metas(&input.attrs)? {
+        if let Meta::NameValue(name_value) = meta {
+            if let (true, Lit::Str(lit_str)) = (name_value.path.is_ident("into"), &name_value.lit) {
+                return Ok(vec![lit_str.parse()?]);
+            }
+        }
+    }
+    let fields: Vec<&syn::Field> = match &input.data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(_) => {
+            let message = "Unions can't be exported";
+            return Err(syn::Error::new_spanned(&input.ident, message));
+        }
+    };
+    let mut result = vec![];
+    for field in fields {
+        if !is_skipped(field)? {
+            result.push(field.ty.clone());
+        }
+    }
+    Ok(result)
+}
+
+fn is_skipped(field: &syn::Field) -> syn::Result<bool> {
+    let mut skip_serializing = false;
+    let mut skip_deserializing = false;
+    for meta in serde_metas(&field.attrs)? {
+        if let Meta::Path(path) = meta {
+            if path.is_ident("skip") {
+                return Ok(true);
+            }
+            skip_serializing |= path.is_ident("skip_serializing");
+            skip_deserializing |= path.is_ident("skip_deserializing");
+        }
+    }
+    Ok(skip_serializing && skip_deserializing)
+}
+
+/// All items inside of `#[serde(...)]` attributes.
+fn serde_metas(attrs: &[syn::Attribute]) -> syn::Result<Vec<Meta>> {
+    let mut result = vec![];
+    for attr in attrs {
+        if !attr.path.is_ident("serde") {
+            continue;
+        }
+        if let Meta::List(list) = attr.parse_meta()? {
+            for nested in list.nested {
+                if let NestedMeta::Meta(meta) = nested {
+                    result.push(meta);
+                }
+            }
+        }
+    }
+    Ok(result)
+}
@@ -0,0 +1,391 @@
+//! Serde attributes that change how a type is written to JSON, and our own
+//! `#[elm(...)]` attributes.
+
+use syn::{Lit, Meta, NestedMeta};
+
+use crate::Result;
+
+/// The `#[serde(...)]` attributes on a container, variant or field that we
+/// understand. Everything else is ignored.
+#[derive(Debug, Default)]
+pub struct SerdeAttributes {
+    /// `#[serde(rename = "name")]`
+    pub rename: Option<String>,
+    /// `#[serde(rename_all = "camelCase")]`
+    pub rename_all: Option<RenameRule>,
+    /// `#[serde(rename_all_fields = "camelCase")]` on an enum applies to the
+    /// fields of all struct-like variants.
+    pub rename_all_fields: Option<RenameRule>,
+    /// `#[serde(tag = "type")]` on an enum.
+    pub tag: Option<String>,
+    /// `#[serde(content = "c")]` next to `tag` on an enum.
+    pub content: Option<String>,
+    /// `#[serde(untagged)]` on an enum.
+    pub untagged: bool,
+    /// `#[serde(default)]` or `#[serde(default = "path")]` on a struct or field.
+    pub default: bool,
+    /// `#[serde(skip_serializing_if = "path")]` on a field.
+    pub skip_serializing_if: bool,
+    /// `#[serde(skip)]` on a field.
+    pub skip: bool,
+    /// `#[serde(skip_serializing)]` on a field.
+    pub skip_serializing: bool,
+    /// `#[serde(skip_deserializing)]` on a field.
+    pub skip_deserializing: bool,
+    /// `#[serde(flatten)]` on a field.
+    pub flatten: bool,
+    /// `#[serde(transparent)]` on a struct with a single field.
+    pub transparent: bool,
+    /// `#[serde(from = "Type")]` on a container.
+    pub from: Option<syn::Type>,
+    /// `#[serde(try_from = "Type")]` on a container.
+    pub try_from: Option<syn::Type>,
+    /// `#[serde(into = "Type")]` on a container.
+    pub into: Option<syn::Type>,
+    /// `#[serde(alias = "name")]` on a field or variant, can be repeated.
+    pub aliases: Vec<String>,
+    /// `#[serde(other)]` on a unit variant.
+    pub other: bool,
+}
+
+impl SerdeAttributes {
+    pub fn parse(attrs: &[syn::Attribute]) -> Result<SerdeAttributes> {
+        let mut result = SerdeAttributes::default();
+        for meta in serde_metas(attrs)? {
+            if let Meta::Path(path) = &meta {
+                if path.is_ident("untagged") {
+                    result.untagged = true;
+                } else if path.is_ident("default") {
+                    result.default = true;
+                } else if path.is_ident("skip") {
+                    result.skip = true;
+                } else if path.is_ident("skip_serializing") {
+                    result.skip_serializing = true;
+                } else if path.is_ident("skip_deserializing") {
+                    result.skip_deserializing = true;
+                } else if path.is_ident("flatten") {
+                    result.flatten = true;
+                } else if path.is_ident("transparent") {
+                    result.transparent = true;
+                } else if path.is_ident("other") {
+                    result.other = true;
+                }
+            }
+            if let Meta::NameValue(name_value) = &meta {
+                if name_value.path.is_ident("rename") {
+                    result.rename = Some(string_value(&name_value.lit)?);
+                } else if name_value.path.is_ident("rename_all") {
+                    result.rename_all = Some(RenameRule::parse(&name_value.lit)?);
+                } else if name_value.path.is_ident("rename_all_fields") {
+                    result.rename_all_fields = Some(RenameRule::parse(&name_value.lit)?);
+                } else if name_value.path.is_ident("tag") {
+                    result.tag = Some(string_value(&name_value.lit)?);
+                } else if name_value.path.is_ident("default") {
+                    result.default = true;
+                } else if name_value.path.is_ident("skip_serializing_if") {
+                    result.skip_serializing_if = true;
+                } else if name_value.path.is_ident("content") {
+                    result.content = Some(string_value(&name_value.lit)?);
+                } else if name_value.path.is_ident("from") {
+                    result.from = Some(type_value(&name_value.lit)?);
+                } else if name_value.path.is_ident("try_from") {
+                    result.try_from = Some(type_value(&name_value.lit)?);
+                } else if name_value.path.is_ident("into") {
+                    result.into = Some(type_value(&name_value.lit)?);
+                } else if name_value.path.is_ident("alias") {
+                    result.aliases.push(string_value(&name_value.lit)?);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// The type that is written to JSON instead of the container, when it is
+    /// converted with `into`. Elm only gets one type, so converting from a
+    /// different type is an error. `from` or `try_from` alone only change
+    /// how the container is read, it is still written as its fields.
+    pub fn conversion_type(&self) -> Result<Option<&syn::Type>> {
+        let from = self.from.as_ref().or(self.try_from.as_ref());
+        match (self.into.as_ref(), from) {
+            (Some(into), Some(from)) if into != from => {
+                let message = "Converting from and into different types is not supported";
+                Err(syn::Error::new_spanned(into, message).into())
+            }
+            (into, _) => Ok(into),
+        }
+    }
+}
+
+/// serde_repr writes C-like enums as their discriminant. The plain serde
+/// derives write the variant name, whatever the `#[repr(...)]` is.
+///
+/// A derive macro doesn't see the `#[derive(...)]` list of its item, so
+/// types described by `#[derive(ElmExport)]` need `#[elm(integer)]` instead.
+pub fn is_integer_enum(attrs: &[syn::Attribute]) -> Result<bool> {
+    if ElmAttributes::parse(attrs)?.integer {
+        return Ok(true);
+    }
+    Ok(derived_traits(attrs)?
+        .iter()
+        .any(|path| path == "Serialize_repr" || path == "Deserialize_repr"))
+}
+
+/// Names of the traits in `#[derive(...)]`, without their module path.
+pub fn derived_traits(attrs: &[syn::Attribute]) -> Result<Vec<String>> {
+    paths_in(attrs, "derive")
+}
+
+/// Last segments of the paths in attributes like `#[derive(serde::Serialize)]`.
+fn paths_in(attrs: &[syn::Attribute], attribute: &str) -> Result<Vec<String>> {
+    let mut result = vec![];
+    for attr in attrs {
+        if !attr.path.is_ident(attribute) {
+            continue;
+        }
+        if let Meta::List(list) = attr.parse_meta()? {
+            for nested in list.nested {
+                if let NestedMeta::Meta(Meta::Path(path)) = nested {
+                    if let Some(segment) = path.segments.last() {
+                        result.push(segment.ident.to_string());
+                    }
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// All items inside of `#[serde(...)]` attributes.
+fn serde_metas(attrs: &[syn::Attribute]) -> Result<Vec<Meta>> {
+    let mut result = vec![];
+    for attr in attrs {
+        if !attr.path.is_ident("serde") {
+            continue;
+        }
+        if let Meta::List(list) = attr.parse_meta()? {
+            for nested in list.nested {
+                if let NestedMeta::Meta(meta) = nested {
+                    result.push(meta);
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+fn string_value(lit: &Lit) -> Result<String> {
+    match lit {
+        Lit::Str(lit_str) => Ok(lit_str.value()),
+        _ => Err(syn::Error::new_spanned(lit, "Expected a string").into()),
+    }
+}
+
+fn type_value(lit: &Lit) -> Result<syn::Type> {
+    match lit {
+        Lit::Str(lit_str) => Ok(lit_str.parse()?),
+        _ => Err(syn::Error::new_spanned(lit, "Expected a type in a string").into()),
+    }
+}
+
+/// The `#[elm(...)]` attributes on a type that derives `ElmExport`.
+#[derive(Debug, Default)]
+pub struct ElmAttributes {
+    /// `#[elm(opaque)]` on a newtype generates an opaque custom type instead
+    /// of a type alias.
+    pub opaque: bool,
+    /// `#[elm(integer)]` on a C-like enum writes it as its discriminant, like
+    /// serde_repr does.
+    pub integer: bool,
+}
+
+impl ElmAttributes {
+    pub fn parse(attrs: &[syn::Attribute]) -> Result<ElmAttributes> {
+        let mut result = ElmAttributes::default();
+        for attr in attrs {
+            if !attr.path.is_ident("elm") {
+                continue;
+            }
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(syn::Error::new_spanned(meta, "Expected #[elm(...)]").into()),
+            };
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("opaque") => {
+                        result.opaque = true;
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("integer") => {
+                        result.integer = true;
+                    }
+                    other => {
+                        return Err(syn::Error::new_spanned(other, "Unknown elm attribute").into())
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// The case conversions of `#[serde(rename_all = "...")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn parse(lit: &Lit) -> Result<RenameRule> {
+        let rule = match string_value(lit)?.as_str() {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            other => {
+                let message = format!("Unknown rename rule {:?}", other);
+                return Err(syn::Error::new_spanned(lit, message).into());
+            }
+        };
+        Ok(rule)
+    }
+
+    /// Renames a variant, which is PascalCase in rust.
+    pub fn apply_to_variant(&self, variant: &str) -> String {
+        match self {
+            RenameRule::Pascal => variant.to_string(),
+            RenameRule::Lower => variant.to_ascii_lowercase(),
+            RenameRule::Upper => variant.to_ascii_uppercase(),
+            RenameRule::Camel => {
+                let mut chars = variant.chars();
+                match chars.next() {
+                    Some(first) => first.to_lowercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            }
+            RenameRule::Snake => {
+                let mut snake = String::new();
+                for (i, c) in variant.char_indices() {
+                    if i > 0 && c.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(c.to_ascii_lowercase());
+                }
+                snake
+            }
+            RenameRule::ScreamingSnake => RenameRule::Snake
+                .apply_to_variant(variant)
+                .to_ascii_uppercase(),
+            RenameRule::Kebab => RenameRule::Snake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+            RenameRule::ScreamingKebab => RenameRule::ScreamingSnake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+
+    /// Renames a field, which is snake_case in rust.
+    pub fn apply_to_field(&self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_string(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for c in field.chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(c);
+                    }
+                }
+                pascal
+            }
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply_to_field(field);
+                RenameRule::Camel.apply_to_variant(&pascal)
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.replace('_', "-").to_ascii_uppercase(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use RenameRule::*;
+
+    #[test]
+    fn test_rename_variant() {
+        let renamed: Vec<String> = [
+            Lower,
+            Upper,
+            Pascal,
+            Camel,
+            Snake,
+            ScreamingSnake,
+            Kebab,
+            ScreamingKebab,
+        ]
+        .iter()
+        .map(|rule| rule.apply_to_variant("VeryTasty"))
+        .collect();
+        assert_eq!(
+            renamed,
+            [
+                "verytasty",
+                "VERYTASTY",
+                "VeryTasty",
+                "veryTasty",
+                "very_tasty",
+                "VERY_TASTY",
+                "very-tasty",
+                "VERY-TASTY"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rename_field() {
+        let renamed: Vec<String> = [
+            Lower,
+            Upper,
+            Pascal,
+            Camel,
+            Snake,
+            ScreamingSnake,
+            Kebab,
+            ScreamingKebab,
+        ]
+        .iter()
+        .map(|rule| rule.apply_to_field("very_tasty"))
+        .collect();
+        assert_eq!(
+            renamed,
+            [
+                "very_tasty",
+                "VERY_TASTY",
+                "VeryTasty",
+                "veryTasty",
+                "very_tasty",
+                "VERY_TASTY",
+                "very-tasty",
+                "VERY-TASTY"
+            ]
+        );
+    }
+}
@@ -1,20 +1,42 @@
 use std::{
     fs::{self, File},
     io::Read,
+    path::Path,
 };
 
 use clap::Parser;
-use rust_elm_typegen::{ElmFile, RustFile};
+use rust_elm_typegen::{ElmFile, FloatMode, RustFile, TypeGenOptions};
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
-    #[clap(short, long)]
-    input: String,
+    /// A single rust file.
+    #[clap(short, long, required_unless_present = "crate-root")]
+    input: Option<String>,
+    /// The root of a crate, like src/lib.rs. Modules declared with `mod` are
+    /// read as well.
+    #[clap(long, conflicts_with = "input", value_name = "FILE")]
+    crate_root: Option<String>,
+    /// The elm file, or the source directory with --module-tree.
     #[clap(short, long)]
     output: String,
+    /// The elm module, or the prefix of all modules with --module-tree.
     #[clap(short, long)]
     module: String,
+    /// Write one elm module for each rust module, like `Api.Game.Cards` for
+    /// `crate::game::cards` with `--module Api`.
+    #[clap(long)]
+    module_tree: bool,
+    /// Decode floats as `Maybe Float`, because serde_json writes NaN and infinity as null.
+    #[clap(long)]
+    lenient_floats: bool,
+    /// Fail decoding fixed size arrays when the list has a different length.
+    #[clap(long)]
+    check_array_length: bool,
+    /// Generate an opaque custom type instead of a type alias for this
+    /// newtype struct, like `#[elm(opaque)]` does. Can be given multiple times.
+    #[clap(long, value_name = "TYPE")]
+    opaque: Vec<String>,
 }
 
 fn main() {
@@ -22,15 +44,49 @@ fn main() {
 
     println!("{:?}", args);
 
-    // Read input file
-    let mut rust_file = File::open(args.input).expect("Failed to open file");
-    let mut rust_file_content = String::new();
-    rust_file
-        .read_to_string(&mut rust_file_content)
-        .expect("Failed to read file");
+    let options = TypeGenOptions {
+        float_mode: if args.lenient_floats {
+            FloatMode::Lenient
+        } else {
+            FloatMode::Strict
+        },
+        check_array_length: args.check_array_length,
+        opaque_newtypes: args.opaque.into_iter().collect(),
+    };
+    let rust_file = match (args.input, args.crate_root) {
+        (_, Some(crate_root)) => {
+            RustFile::parse_crate(Path::new(&crate_root), &options).expect("Failed to parse crate")
+        }
+        (Some(input), None) => {
+            // Read input file
+            let mut rust_file = File::open(input).expect("Failed to open file");
+            let mut rust_file_content = String::new();
+            rust_file
+                .read_to_string(&mut rust_file_content)
+                .expect("Failed to read file");
+
+            let ast = syn::parse_file(&rust_file_content).expect("Failed to parse file");
+            RustFile::parse_with_options(&ast, &options).expect("Failed to parse file")
+        }
+        (None, None) => unreachable!("clap requires one of them"),
+    };
+    for warning in &rust_file.warnings {
+        eprintln!("Warning: {}", warning);
+    }
 
-    let ast = syn::parse_file(&rust_file_content).expect("Failed to parse file");
-    let rust_file = RustFile::parse(&ast).expect("Failed to parse file");
+    if args.module_tree {
+        let elm_files = rust_file
+            .elm_modules(&args.module)
+            .expect("Failed to split modules");
+        for elm_file in elm_files {
+            let path = Path::new(&args.output).join(elm_file.file_path());
+            if let Some(directory) = path.parent() {
+                fs::create_dir_all(directory).expect("Failed to create directory");
+            }
+            fs::write(path, elm_file.generate_file_content()).expect("Failed to write file");
+        }
+        return;
+    }
 
     let elm_file_object = ElmFile {
         name: args.module,
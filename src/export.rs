@@ -0,0 +1,313 @@
+//! Elm definitions collected from `ElmExport` implementations at runtime,
+//! instead of from the source code.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::names::NameTable;
+use crate::{
+    extract_converted_enum, extract_elm_enum, extract_elm_struct, resolve_flattened_maps,
+    resolve_map_keys, ElmEnum, ElmExport, ElmFile, ElmStruct, ElmType, Identifier, Result,
+    TypeGenError, TypeGenOptions,
+};
+
+/// The structs and enums that exported types need, in the order they were
+/// first seen.
+///
+/// ```
+/// use rust_elm_typegen::{ElmDefinitions, ElmExport};
+///
+/// #[derive(ElmExport)]
+/// struct Login {
+///     user: String,
+/// }
+///
+/// let mut definitions = ElmDefinitions::default();
+/// definitions.export::<Login>();
+/// let elm_file = definitions.into_elm_file("Login").unwrap();
+/// assert!(elm_file.generate_file_content().contains("type alias Login ="));
+/// ```
+#[derive(Debug, Default)]
+pub struct ElmDefinitions {
+    options: TypeGenOptions,
+    structs: Vec<ElmStruct>,
+    enums: Vec<ElmEnum>,
+    /// The rust module of every definition. Types are referenced by their
+    /// name alone, so two with the same name can't both be exported.
+    modules: HashMap<Identifier, String>,
+    /// Errors are kept until `into_elm_file`, so the derived code doesn't
+    /// need to handle them.
+    error: Option<crate::TypeGenError>,
+}
+
+impl ElmDefinitions {
+    pub fn new(options: TypeGenOptions) -> ElmDefinitions {
+        ElmDefinitions {
+            options,
+            ..ElmDefinitions::default()
+        }
+    }
+
+    /// Adds `T` and every type it references.
+    pub fn export<T: ElmExport + ?Sized>(&mut self) {
+        T::collect_definitions(self);
+    }
+
+    /// Adds the struct or enum in `source` from the rust module `module`,
+    /// which `#[derive(ElmExport)]` passes in. Its field types are replaced by names from `described`,
+    /// together with their elm type from their own `ElmExport` impl. Returns
+    /// false if the type was added before, so recursive types don't loop
+    /// forever.
+    pub fn add_item(
+        &mut self,
+        module: &str,
+        source: &str,
+        described: Vec<(&str, ElmType)>,
+    ) -> bool {
+        match self.parse_item(module, source, described) {
+            Ok(added) => added,
+            Err(error) => {
+                self.error.get_or_insert(error);
+                false
+            }
+        }
+    }
+
+    fn parse_item(
+        &mut self,
+        module: &str,
+        source: &str,
+        described: Vec<(&str, ElmType)>,
+    ) -> Result<bool> {
+        let item: syn::Item = syn::parse_str(source)?;
+        let name = match &item {
+            syn::Item::Struct(item_struct) => &item_struct.ident,
+            syn::Item::Enum(item_enum) => &item_enum.ident,
+            _ => {
+                let message = "Only structs and enums can be exported";
+                return Err(syn::Error::new_spanned(&item, message).into());
+            }
+        };
+        let name = Identifier(name.to_string());
+        match self.modules.get(&name) {
+            Some(known) if known == module => return Ok(false),
+            Some(known) => {
+                return Err(TypeGenError::NameCollision {
+                    first: format!("{}::{}", known, name.0),
+                    second: format!("{}::{}", module, name.0),
+                    name: name.0,
+                });
+            }
+            None => {}
+        }
+        self.modules.insert(name.clone(), module.to_string());
+        let described = described
+            .into_iter()
+            .map(|(name, ty)| (Identifier::new(name), ty))
+            .collect();
+        let names = NameTable::described(described);
+        let names = names.scope(&[]);
+        match &item {
+            syn::Item::Struct(item_struct) => {
+                self.structs
+                    .push(extract_elm_struct(item_struct, names, &self.options)?);
+            }
+            syn::Item::Enum(item_enum) => {
+                if let Some(elm_struct) = extract_converted_enum(item_enum, names, &self.options)? {
+                    self.structs.push(elm_struct);
+                } else if let Some(elm_enum) = extract_elm_enum(item_enum, names, &self.options)? {
+                    self.enums.push(elm_enum);
+                }
+            }
+            _ => unreachable!("Checked above"),
+        }
+        Ok(true)
+    }
+
+    pub fn into_elm_file(mut self, name: impl Into<String>) -> Result<ElmFile> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        resolve_flattened_maps(&mut self.structs, &mut self.enums);
+        resolve_map_keys(&mut self.structs, &mut self.enums)?;
+        self.check_defined()?;
+        Ok(ElmFile {
+            name: name.into(),
+            structs: self.structs,
+            enums: self.enums,
+        })
+    }
+
+    /// Types with a hand-written `ElmExport` impl are referenced by name
+    /// without adding a definition, which elm would reject.
+    fn check_defined(&self) -> Result<()> {
+        let defined: HashSet<&Identifier> = (self.structs.iter().map(|s| &s.name))
+            .chain(self.enums.iter().map(|e| &e.name))
+            .collect();
+        let referenced = (self.structs.iter())
+            .map(|s| (&s.name, s.referenced_types()))
+            .chain(self.enums.iter().map(|e| (&e.name, e.referenced_types())));
+        for (referenced_by, names) in referenced {
+            if let Some(name) = names.into_iter().find(|name| !defined.contains(name)) {
+                return Err(TypeGenError::UndefinedType {
+                    name: name.0,
+                    referenced_by: referenced_by.0.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+macro_rules! export_as {
+    ($elm_type:expr => $($rust_type:ty),*) => {
+        $(
+            impl ElmExport for $rust_type {
+                fn elm_type() -> ElmType {
+                    $elm_type
+                }
+            }
+        )*
+    };
+}
+
+export_as!(ElmType::Int => u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+export_as!(ElmType::Float => f32, f64);
+export_as!(ElmType::String => String, str, char);
+export_as!(ElmType::Bool => bool);
+export_as!(ElmType::Unit => ());
+
+/// Types that serde writes like the type they wrap.
+macro_rules! export_transparent {
+    ($($rust_type:ty),*) => {
+        $(
+            impl<T: ElmExport + ?Sized> ElmExport for $rust_type {
+                fn elm_type() -> ElmType {
+                    T::elm_type()
+                }
+
+                fn collect_definitions(definitions: &mut ElmDefinitions) {
+                    T::collect_definitions(definitions);
+                }
+            }
+        )*
+    };
+}
+
+export_transparent!(&T, &mut T, Box<T>, Rc<T>, Arc<T>);
+
+impl<T: ElmExport + ToOwned + ?Sized> ElmExport for Cow<'_, T> {
+    fn elm_type() -> ElmType {
+        T::elm_type()
+    }
+
+    fn collect_definitions(definitions: &mut ElmDefinitions) {
+        T::collect_definitions(definitions);
+    }
+}
+
+impl<T: ElmExport> ElmExport for Option<T> {
+    fn elm_type() -> ElmType {
+        ElmType::Maybe(Box::new(T::elm_type()))
+    }
+
+    fn collect_definitions(definitions: &mut ElmDefinitions) {
+        T::collect_definitions(definitions);
+    }
+}
+
+/// Collections that serde writes as JSON arrays.
+macro_rules! export_list {
+    ($($rust_type:ty),*) => {
+        $(
+            impl<T: ElmExport> ElmExport for $rust_type {
+                fn elm_type() -> ElmType {
+                    ElmType::List(Box::new(T::elm_type()))
+                }
+
+                fn collect_definitions(definitions: &mut ElmDefinitions) {
+                    T::collect_definitions(definitions);
+                }
+            }
+        )*
+    };
+}
+
+export_list!([T], Vec<T>, VecDeque<T>, LinkedList<T>, BTreeSet<T>);
+
+impl<T: ElmExport, S> ElmExport for HashSet<T, S> {
+    fn elm_type() -> ElmType {
+        ElmType::List(Box::new(T::elm_type()))
+    }
+
+    fn collect_definitions(definitions: &mut ElmDefinitions) {
+        T::collect_definitions(definitions);
+    }
+}
+
+/// A `List` unless `TypeGenOptions::check_array_length` is set.
+impl<T: ElmExport, const N: usize> ElmExport for [T; N] {
+    fn elm_type() -> ElmType {
+        ElmType::Array(Box::new(T::elm_type()), N)
+    }
+
+    fn collect_definitions(definitions: &mut ElmDefinitions) {
+        T::collect_definitions(definitions);
+    }
+}
+
+impl<K: ElmExport, V: ElmExport, S> ElmExport for HashMap<K, V, S> {
+    fn elm_type() -> ElmType {
+        ElmType::Dict(Box::new(K::elm_type()), Box::new(V::elm_type()))
+    }
+
+    fn collect_definitions(definitions: &mut ElmDefinitions) {
+        K::collect_definitions(definitions);
+        V::collect_definitions(definitions);
+    }
+}
+
+impl<K: ElmExport, V: ElmExport> ElmExport for BTreeMap<K, V> {
+    fn elm_type() -> ElmType {
+        ElmType::Dict(Box::new(K::elm_type()), Box::new(V::elm_type()))
+    }
+
+    fn collect_definitions(definitions: &mut ElmDefinitions) {
+        K::collect_definitions(definitions);
+        V::collect_definitions(definitions);
+    }
+}
+
+impl<T: ElmExport, E: ElmExport> ElmExport for std::result::Result<T, E> {
+    fn elm_type() -> ElmType {
+        ElmType::Result(Box::new(T::elm_type()), Box::new(E::elm_type()))
+    }
+
+    fn collect_definitions(definitions: &mut ElmDefinitions) {
+        T::collect_definitions(definitions);
+        E::collect_definitions(definitions);
+    }
+}
+
+macro_rules! export_tuple {
+    ($($element:ident),*) => {
+        impl<$($element: ElmExport),*> ElmExport for ($($element,)*) {
+            fn elm_type() -> ElmType {
+                ElmType::Tuple(vec![$($element::elm_type()),*])
+            }
+
+            fn collect_definitions(definitions: &mut ElmDefinitions) {
+                $($element::collect_definitions(definitions);)*
+            }
+        }
+    };
+}
+
+export_tuple!(A);
+export_tuple!(A, B);
+export_tuple!(A, B, C);
+export_tuple!(A, B, C, D);
+export_tuple!(A, B, C, D, E);
+export_tuple!(A, B, C, D, E, F);
@@ -0,0 +1,316 @@
+//! Elm helper functions that some types need in their decoders and encoders.
+//! They are only written into the generated file when a type uses them.
+
+use indoc::indoc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ElmHelper {
+    DecodeDictWithKeys,
+    DecodeKeyValueList,
+    EncodeKeyValueList,
+    KeyFromStringWith,
+    KeyToStringWith,
+    BoolKeyFromString,
+    BoolKeyToString,
+    DecodeListOfLength,
+    /// Decoder and encoder for serde's `{"Ok": ...}` / `{"Err": ...}` layout,
+    /// named so they don't clash with those of a crate type called `Result`.
+    Result,
+    /// Fields of an encoded object, to merge them into another object.
+    ObjectPairs,
+    /// Decodes the keys of an object that no other field uses, for a
+    /// flattened map.
+    DecodeRemainingFields,
+    /// Decodes the first of several keys that is in an object, for fields
+    /// with aliases.
+    DecodeFirstField,
+    /// Decoder and encoder for a tuple with this many elements. Elm only has
+    /// tuples with two or three elements, other sizes get a record type.
+    Tuple(usize),
+}
+
+/// Whether a tuple of this size needs the `TupleN` record, because elm has no
+/// tuple type for it.
+pub fn is_tuple_record(size: usize) -> bool {
+    !(2..=3).contains(&size)
+}
+
+/// Type variables for the elements of a tuple: a, b, c, ...
+fn type_variables(size: usize) -> Vec<char> {
+    ('a'..='z').take(size).collect()
+}
+
+impl ElmHelper {
+    /// Other helpers that are called by this helper.
+    pub fn dependencies(&self) -> &'static [ElmHelper] {
+        match self {
+            ElmHelper::DecodeDictWithKeys => &[ElmHelper::DecodeKeyValueList],
+            _ => &[],
+        }
+    }
+
+    pub fn definition(&self) -> String {
+        match self {
+            ElmHelper::Tuple(size) => tuple_definition(*size),
+            _ => self.static_definition().to_string(),
+        }
+    }
+
+    fn static_definition(&self) -> &'static str {
+        match self {
+            ElmHelper::DecodeDictWithKeys => indoc! {r#"
+                decodeDictWithKeys : (String -> Maybe comparable) -> Json.Decode.Decoder v -> Json.Decode.Decoder (Dict comparable v)
+                decodeDictWithKeys keyFromString valueDecoder =
+                    Json.Decode.map Dict.fromList (decodeKeyValueList keyFromString valueDecoder)
+            "#},
+            ElmHelper::DecodeKeyValueList => indoc! {r#"
+                -- Elm can only use comparable types like Int and String as Dict keys.
+                -- Maps with other keys, like enums, are represented as List ( k, v ).
+                decodeKeyValueList : (String -> Maybe k) -> Json.Decode.Decoder v -> Json.Decode.Decoder (List ( k, v ))
+                decodeKeyValueList keyFromString valueDecoder =
+                    let
+                        parseEntry ( key, value ) =
+                            Maybe.map (\k -> ( k, value )) (keyFromString key)
+                    in
+                    Json.Decode.keyValuePairs valueDecoder
+                        |> Json.Decode.andThen
+                            (\pairs ->
+                                let
+                                    entries =
+                                        List.filterMap parseEntry pairs
+                                in
+                                if List.length entries == List.length pairs then
+                                    Json.Decode.succeed entries
+
+                                else
+                                    Json.Decode.fail "Unexpected map key"
+                            )
+            "#},
+            ElmHelper::EncodeKeyValueList => indoc! {r#"
+                encodeKeyValueList : (k -> String) -> (v -> Json.Encode.Value) -> List ( k, v ) -> Json.Encode.Value
+                encodeKeyValueList keyToString valueEncoder pairs =
+                    Json.Encode.object (List.map (\( k, v ) -> ( keyToString k, valueEncoder v )) pairs)
+            "#},
+            ElmHelper::KeyFromStringWith => indoc! {r#"
+                -- Numbers and bools are written as their JSON text, like "5" or "true".
+                keyFromStringWith : Json.Decode.Decoder k -> String -> Maybe k
+                keyFromStringWith keyDecoder key =
+                    case Json.Decode.decodeValue keyDecoder (Json.Encode.string key) of
+                        Ok decoded ->
+                            Just decoded
+
+                        Err _ ->
+                            Json.Decode.decodeString keyDecoder key
+                                |> Result.toMaybe
+            "#},
+            ElmHelper::KeyToStringWith => indoc! {r#"
+                -- serde_json writes number and bool keys as their JSON text.
+                keyToStringWith : (k -> Json.Encode.Value) -> k -> String
+                keyToStringWith keyEncoder key =
+                    let
+                        value =
+                            keyEncoder key
+                    in
+                    case Json.Decode.decodeValue Json.Decode.string value of
+                        Ok string ->
+                            string
+
+                        Err _ ->
+                            Json.Encode.encode 0 value
+            "#},
+            ElmHelper::BoolKeyFromString => indoc! {r#"
+                boolKeyFromString : String -> Maybe Bool
+                boolKeyFromString key =
+                    case key of
+                        "true" ->
+                            Just True
+
+                        "false" ->
+                            Just False
+
+                        _ ->
+                            Nothing
+            "#},
+            ElmHelper::BoolKeyToString => indoc! {r#"
+                boolKeyToString : Bool -> String
+                boolKeyToString key =
+                    if key then
+                        "true"
+
+                    else
+                        "false"
+            "#},
+            ElmHelper::DecodeListOfLength => indoc! {r#"
+                decodeListOfLength : Int -> Json.Decode.Decoder a -> Json.Decode.Decoder (List a)
+                decodeListOfLength length decoder =
+                    Json.Decode.list decoder
+                        |> Json.Decode.andThen
+                            (\list ->
+                                if List.length list == length then
+                                    Json.Decode.succeed list
+
+                                else
+                                    Json.Decode.fail ("Expected a list of length " ++ String.fromInt length)
+                            )
+            "#},
+            ElmHelper::Result => indoc! {r#"
+                decodeStdResult : Json.Decode.Decoder e -> Json.Decode.Decoder t -> Json.Decode.Decoder (Result.Result e t)
+                decodeStdResult decodeErr decodeOk =
+                    Json.Decode.oneOf
+                        [ Json.Decode.map Result.Ok (Json.Decode.field "Ok" decodeOk)
+                        , Json.Decode.map Result.Err (Json.Decode.field "Err" decodeErr)
+                        ]
+
+                encodeStdResult : (e -> Json.Encode.Value) -> (t -> Json.Encode.Value) -> Result.Result e t -> Json.Encode.Value
+                encodeStdResult encodeErr encodeOk result =
+                    case result of
+                        Result.Ok value ->
+                            Json.Encode.object [ ( "Ok", encodeOk value ) ]
+
+                        Result.Err error ->
+                            Json.Encode.object [ ( "Err", encodeErr error ) ]
+            "#},
+            ElmHelper::ObjectPairs => indoc! {r#"
+                objectPairs : Json.Encode.Value -> List ( String, Json.Encode.Value )
+                objectPairs value =
+                    Json.Decode.decodeValue (Json.Decode.keyValuePairs Json.Decode.value) value
+                        |> Result.withDefault []
+            "#},
+            ElmHelper::DecodeRemainingFields => indoc! {r#"
+                decodeRemainingFields : List String -> Json.Decode.Decoder v -> Json.Decode.Decoder (Dict String v)
+                decodeRemainingFields knownKeys valueDecoder =
+                    let
+                        decodeEntry ( key, value ) =
+                            case Json.Decode.decodeValue valueDecoder value of
+                                Ok decoded ->
+                                    Json.Decode.succeed ( key, decoded )
+
+                                Err error ->
+                                    Json.Decode.fail (Json.Decode.errorToString error)
+                    in
+                    Json.Decode.keyValuePairs Json.Decode.value
+                        |> Json.Decode.andThen
+                            (\pairs ->
+                                pairs
+                                    |> List.filter (\( key, _ ) -> not (List.member key knownKeys))
+                                    |> List.map decodeEntry
+                                    |> List.foldr (Json.Decode.map2 (::)) (Json.Decode.succeed [])
+                                    |> Json.Decode.map Dict.fromList
+                            )
+            "#},
+            ElmHelper::DecodeFirstField => indoc! {r#"
+                decodeFirstField : List String -> Json.Decode.Decoder v -> Json.Decode.Decoder (Maybe v)
+                decodeFirstField keys valueDecoder =
+                    case keys of
+                        [] ->
+                            Json.Decode.succeed Nothing
+
+                        key :: otherKeys ->
+                            Json.Decode.maybe (Json.Decode.field key Json.Decode.value)
+                                |> Json.Decode.andThen
+                                    (\found ->
+                                        case found of
+                                            Just _ ->
+                                                Json.Decode.map Just (Json.Decode.field key valueDecoder)
+
+                                            Nothing ->
+                                                decodeFirstField otherKeys valueDecoder
+                                    )
+            "#},
+            ElmHelper::Tuple(_) => unreachable!("Tuple helpers depend on their size"),
+        }
+    }
+}
+
+fn tuple_definition(size: usize) -> String {
+    let variables = type_variables(size);
+    let type_ref = if is_tuple_record(size) {
+        let mut type_ref = format!("(Tuple{}", size);
+        for variable in &variables {
+            type_ref.push_str(&format!(" {}", variable));
+        }
+        type_ref.push(')');
+        type_ref
+    } else {
+        let elements: Vec<String> = variables.iter().map(char::to_string).collect();
+        format!("( {} )", elements.join(", "))
+    };
+    let decoders: Vec<String> = variables
+        .iter()
+        .map(|v| format!("decode{}", v.to_ascii_uppercase()))
+        .collect();
+    let encoders: Vec<String> = variables
+        .iter()
+        .map(|v| format!("encode{}", v.to_ascii_uppercase()))
+        .collect();
+
+    let mut output = String::new();
+    if is_tuple_record(size) {
+        // type alias Tuple4 a b c d =
+        //     { item0 : a
+        //     , item1 : b
+        //     ...
+        output.push_str(&format!(
+            "type alias {} =\n",
+            &type_ref[1..type_ref.len() - 1]
+        ));
+        for (i, variable) in variables.iter().enumerate() {
+            let prefix = if i == 0 { "{" } else { "," };
+            output.push_str(&format!("    {} item{} : {}\n", prefix, i, variable));
+        }
+        output.push_str("    }\n\n");
+    }
+
+    output.push_str(&format!("decodeTuple{} : ", size));
+    for variable in &variables {
+        output.push_str(&format!("Json.Decode.Decoder {} -> ", variable));
+    }
+    output.push_str(&format!("Json.Decode.Decoder {}\n", type_ref));
+    output.push_str(&format!("decodeTuple{} {} =\n", size, decoders.join(" ")));
+    if is_tuple_record(size) {
+        output.push_str(&format!("    Json.Decode.succeed Tuple{}\n", size));
+        for (i, decoder) in decoders.iter().enumerate() {
+            output.push_str(&format!(
+                "        |> Json.Decode.Pipeline.custom (Json.Decode.index {} {})\n",
+                i, decoder
+            ));
+        }
+    } else {
+        let constructor = if size == 2 {
+            "Tuple.pair".to_string()
+        } else {
+            "(\\a b c -> ( a, b, c ))".to_string()
+        };
+        output.push_str(&format!("    Json.Decode.map{} {}\n", size, constructor));
+        for (i, decoder) in decoders.iter().enumerate() {
+            output.push_str(&format!("        (Json.Decode.index {} {})\n", i, decoder));
+        }
+    }
+    output.push('\n');
+
+    output.push_str(&format!("encodeTuple{} : ", size));
+    for variable in &variables {
+        output.push_str(&format!("({} -> Json.Encode.Value) -> ", variable));
+    }
+    output.push_str(&format!("{} -> Json.Encode.Value\n", type_ref));
+    let (pattern, elements): (String, Vec<String>) = if is_tuple_record(size) {
+        let elements = (0..size).map(|i| format!("tuple.item{}", i)).collect();
+        ("tuple".to_string(), elements)
+    } else {
+        let elements: Vec<String> = variables.iter().map(char::to_string).collect();
+        (format!("( {} )", elements.join(", ")), elements)
+    };
+    output.push_str(&format!(
+        "encodeTuple{} {} {} =\n",
+        size,
+        encoders.join(" "),
+        pattern
+    ));
+    output.push_str("    Json.Encode.list identity\n");
+    for (i, (encoder, element)) in encoders.iter().zip(&elements).enumerate() {
+        let prefix = if i == 0 { "[" } else { "," };
+        output.push_str(&format!("        {} {} {}\n", prefix, encoder, element));
+    }
+    output.push_str("        ]\n");
+    output
+}
@@ -35,6 +35,21 @@ impl Identifier {
         }
         result
     }
+
+    /// A function for this type, like `decodePerson` for the prefix `decode`.
+    /// Types from another elm module are qualified and so are their
+    /// functions: `Api.Game.decodeCard`.
+    pub fn function_name(&self, prefix: &str) -> String {
+        match self.0.rsplit_once('.') {
+            Some((module, name)) => format!("{}.{}{}", module, prefix, name),
+            None => format!("{}{}", prefix, self.0),
+        }
+    }
+
+    /// The elm module of a qualified type name like `Api.Game.Card`.
+    pub fn module(&self) -> Option<&str> {
+        self.0.rsplit_once('.').map(|(module, _)| module)
+    }
 }
 
 #[cfg(test)]
@@ -48,4 +63,14 @@ mod tests {
         assert_eq!(identifier.camel_case(UpperCase), "SnakeCase");
         assert_eq!(identifier.camel_case(LowerCase), "snakeCase");
     }
+
+    #[test]
+    fn test_function_name() {
+        let identifier = Identifier::new("Card");
+        assert_eq!(identifier.function_name("decode"), "decodeCard");
+        assert_eq!(identifier.module(), None);
+        let identifier = Identifier::new("Api.Game.Card");
+        assert_eq!(identifier.function_name("encode"), "Api.Game.encodeCard");
+        assert_eq!(identifier.module(), Some("Api.Game"));
+    }
 }
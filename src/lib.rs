@@ -1,81 +1,401 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 
 use syn::spanned::Spanned;
 use syn::TypePath;
 use thiserror::Error;
 
+mod attributes;
+mod export;
+mod helpers;
 mod identifier;
-use identifier::Identifier;
+mod module_tree;
+mod modules;
+mod names;
+mod shape;
+use attributes::{ElmAttributes, SerdeAttributes};
+pub use export::ElmDefinitions;
+use helpers::ElmHelper;
+pub use identifier::Identifier;
 use identifier::LetterCase::*;
+use modules::RustModule;
+use names::{ModuleScope, NameTable, ResolvedPath};
 
 #[derive(Error, Debug)]
 pub enum TypeGenError {
     #[error("{0}")]
     Syn(#[from] syn::Error),
-    #[error("Unnamed structs are not supported")]
-    UnnamedStructsNotSupported,
+    #[error("Type {name} is referenced by {referenced_by} but not defined in the input")]
+    UndefinedType { name: String, referenced_by: String },
+    #[error("Failed to read {}: {source}", .path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Variant {variant} of {name} can't be serialized: {reason}")]
+    UnsupportedVariant {
+        name: String,
+        variant: String,
+        reason: String,
+    },
+    #[error("Type {key} is used as a map key by {referenced_by}, but it isn't written as a string, integer or bool")]
+    InvalidMapKey { key: String, referenced_by: String },
+    #[error("Types {first} and {second} would both be called {name} in elm")]
+    NameCollision {
+        name: String,
+        first: String,
+        second: String,
+    },
+    #[error("Elm modules can't import each other in a cycle: {cycle}")]
+    ImportCycle { cycle: String },
 }
 
 pub type Result<T> = std::result::Result<T, TypeGenError>;
 
-/// Marker trait for exported types.
-pub trait ElmExport {}
+/// A type that is exported to elm.
+///
+/// `impl ElmExport for Foo {}` only marks the type for [`RustFile::parse`],
+/// which reads everything else from the source code. `#[derive(ElmExport)]`
+/// also describes the type at runtime, so [`ElmDefinitions`] can collect it
+/// even when it is defined by a macro or in another crate.
+pub trait ElmExport {
+    /// How a value of this type is written in elm. Defaults to a type named
+    /// like the rust type.
+    fn elm_type() -> ElmType {
+        let name = std::any::type_name::<Self>();
+        let name = name.split('<').next().unwrap_or(name);
+        let name = name.rsplit("::").next().unwrap_or(name);
+        ElmType::NamedType(Identifier::new(name), vec![])
+    }
+
+    /// Adds the definition of this type and of every type it references.
+    fn collect_definitions(_definitions: &mut ElmDefinitions) {}
+}
+
+/// `#[derive(ElmExport)]` instead of `impl ElmExport for Foo {}`.
+pub use rust_elm_typegen_derive::ElmExport;
+
+// The derive refers to `::rust_elm_typegen`, which lets us use it in our tests.
+extern crate self as rust_elm_typegen;
+
+/// How `f32` and `f64` fields are mapped to Elm.
+///
+/// serde_json writes NaN and the infinities as `null`, which a plain
+/// `Json.Decode.float` rejects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FloatMode {
+    /// Map to `Float`. Decoding fails when the value is `null`.
+    #[default]
+    Strict,
+    /// Map to `Maybe Float`, where `Nothing` stands for NaN or an infinity.
+    Lenient,
+}
+
+/// Settings that change how rust types are mapped to elm types.
+#[derive(Debug, Clone, Default)]
+pub struct TypeGenOptions {
+    pub float_mode: FloatMode,
+    /// Fixed size arrays like `[u8; 4]` get decoders that fail when the list
+    /// has a different length.
+    pub check_array_length: bool,
+    /// Newtypes like `struct UserId(u64)` become a type alias for the inner
+    /// type. Newtypes named here or marked with `#[elm(opaque)]` become an
+    /// opaque `type UserId = UserId Int`.
+    pub opaque_newtypes: HashSet<String>,
+}
 
+#[derive(Debug)]
 pub struct ElmFile {
     pub name: String,
     pub structs: Vec<ElmStruct>,
     pub enums: Vec<ElmEnum>,
 }
 
-// Float,
 // Char,
 // Bool,
-// Dict(Box<ElmType>, Box<ElmType>),
 // Maybe(Box<ElmType>),
-// Result(Box<ElmType>, Box<ElmType>),
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ElmType {
     Int,
+    Float,
     String,
     Bool,
     List(Box<ElmType>),
+    /// A `List` that must have exactly this length when decoding. Only used
+    /// for `[T; N]` when `TypeGenOptions::check_array_length` is set.
+    Array(Box<ElmType>, usize),
     /// Don't use Maybe(Maybe(x)), because you loose information when serializing.
     Maybe(Box<ElmType>),
-    NamedType(Identifier), // No generics yet.
+    /// Ok and Err type. Elm writes them the other way around: `Result e t`.
+    Result(Box<ElmType>, Box<ElmType>),
+    /// Rust `()`, which serde writes as `null`.
+    Unit,
+    /// Tuples are written as JSON arrays.
+    Tuple(Vec<ElmType>),
+    /// Key and value type. serde_json writes all map keys as strings.
+    /// Keys that Elm can't use in a `Dict` turn into a `List ( k, v )`.
+    Dict(Box<ElmType>, Box<ElmType>),
+    /// A struct or enum, together with its generic arguments.
+    NamedType(Identifier, Vec<ElmType>),
+    /// A generic type parameter like the `T` in `Page<T>`.
+    TypeVariable(Identifier),
 }
 
 #[derive(Debug, Clone)]
 pub struct ElmStruct {
     name: Identifier,
-    fields: Vec<(Identifier, ElmType)>,
+    generics: Vec<Identifier>,
+    fields: StructFields,
+}
+
+/// A named field of a struct or struct-like variant.
+#[derive(Debug, Clone)]
+pub struct ElmField {
+    /// The field name in rust.
+    name: Identifier,
+    /// The key in JSON, after applying serde renames.
+    serialized_name: String,
+    ty: ElmType,
+    /// Elm value to use when the key is missing from the JSON, for fields
+    /// with `#[serde(default)]` or `#[serde(skip_serializing_if = "...")]`.
+    default: Option<String>,
+    /// `#[serde(flatten)]` writes the fields of this value into the parent
+    /// object instead of under its own key.
+    flatten: Option<Flatten>,
+    /// Other keys that are accepted when decoding, from `#[serde(alias = "...")]`.
+    aliases: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+enum Flatten {
+    /// The fields of a struct are inlined.
+    Struct,
+    /// A map with string keys collects all keys that no other field uses.
+    Map { known_keys: Vec<String> },
+}
+
+impl ElmField {
+    pub fn new(name: Identifier, ty: ElmType) -> ElmField {
+        ElmField {
+            serialized_name: name.0.clone(),
+            name,
+            ty,
+            default: None,
+            flatten: None,
+            aliases: vec![],
+        }
+    }
+
+    /// All keys that are accepted when decoding, the serialized name first.
+    fn keys(&self) -> Vec<String> {
+        let mut keys = vec![self.serialized_name.clone()];
+        keys.extend(self.aliases.iter().cloned());
+        keys
+    }
+
+    /// Record field names are camel case in elm.
+    fn elm_name(&self) -> String {
+        self.name.camel_case(LowerCase)
+    }
+
+    fn collect_helpers(&self, output: &mut BTreeSet<ElmHelper>) {
+        match &self.flatten {
+            Some(Flatten::Struct) => {
+                output.insert(ElmHelper::ObjectPairs);
+            }
+            Some(Flatten::Map { .. }) => {
+                output.insert(ElmHelper::ObjectPairs);
+                output.insert(ElmHelper::DecodeRemainingFields);
+            }
+            None => {}
+        }
+        if self.default.is_some() && !self.aliases.is_empty() {
+            output.insert(ElmHelper::DecodeFirstField);
+        }
+    }
+
+    /// Optional fields that are `Nothing` are left out when encoding.
+    fn is_omitted_when_nothing(&self) -> bool {
+        self.default.is_some() && matches!(self.ty, ElmType::Maybe(_))
+    }
+
+    /// Pipeline step that decodes this field, without the leading `|> `.
+    fn pipeline_decoder(&self) -> String {
+        match (&self.flatten, &self.default) {
+            (Some(Flatten::Struct), _) => {
+                format!("Json.Decode.Pipeline.custom {}", self.ty.decoder_ref())
+            }
+            (Some(Flatten::Map { known_keys }), _) => {
+                let value_decoder = match &self.ty {
+                    ElmType::Dict(_, value) => value.decoder_ref(),
+                    _ => unreachable!("Only maps are flattened as maps"),
+                };
+                format!(
+                    "Json.Decode.Pipeline.custom (decodeRemainingFields {} {})",
+                    string_list(known_keys),
+                    value_decoder
+                )
+            }
+            (None, Some(default)) if !self.aliases.is_empty() => format!(
+                "Json.Decode.Pipeline.custom (Json.Decode.map (Maybe.withDefault {}) (decodeFirstField {} {}))",
+                default,
+                string_list(&self.keys()),
+                self.ty.decoder_ref()
+            ),
+            (None, None) if !self.aliases.is_empty() => {
+                let decoders: Vec<String> = self
+                    .keys()
+                    .iter()
+                    .map(|key| format!("Json.Decode.field \"{}\" {}", key, self.ty.decoder_ref()))
+                    .collect();
+                format!(
+                    "Json.Decode.Pipeline.custom (Json.Decode.oneOf [ {} ])",
+                    decoders.join(", ")
+                )
+            }
+            (None, Some(default)) => format!(
+                "Json.Decode.Pipeline.optional \"{}\" {} {}",
+                self.serialized_name,
+                self.ty.decoder_ref(),
+                default
+            ),
+            (None, None) => format!(
+                "Json.Decode.Pipeline.required \"{}\" {}",
+                self.serialized_name,
+                self.ty.decoder_ref()
+            ),
+        }
+    }
+}
+
+/// The shape of a struct decides how serde writes it.
+#[derive(Debug, Clone)]
+pub enum StructFields {
+    /// `struct Person { age: u32 }` is a JSON object.
+    Named(Vec<ElmField>),
+    /// `struct UserId(u64)` is written like the type it wraps.
+    Newtype { inner: ElmType, opaque: bool },
+    /// `struct Point(i32, i32)` is a JSON array.
+    Tuple(Vec<ElmType>),
+    /// `struct Marker;` is written as `null`.
+    Unit,
 }
 
 #[derive(Debug, Clone)]
 pub struct ElmEnum {
     name: Identifier,
+    generics: Vec<Identifier>,
     variants: Vec<ElmEnumVariant>,
+    representation: EnumRepresentation,
+}
+
+/// Where serde puts the variant name of an enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnumRepresentation {
+    /// `{"Moved": {"x": 1}}`, the default.
+    External,
+    /// `#[serde(tag = "type")]` gives `{"type": "Moved", "x": 1}`.
+    Internal { tag: String },
+    /// `#[serde(tag = "t", content = "c")]` gives `{"t": "Moved", "c": {"x": 1}}`.
+    Adjacent { tag: String, content: String },
+    /// `#[serde(untagged)]` only writes the payload: `{"x": 1}`.
+    Untagged,
+    /// serde_repr writes the discriminant of C-like enums: `2`.
+    Integer,
+}
+
+/// Everything that is needed to turn a rust type into an elm type.
+struct Scope<'a> {
+    options: &'a TypeGenOptions,
+    /// Generic type parameters of the struct or enum the type appears in.
+    generics: &'a [Identifier],
+    /// Names of the module the type appears in.
+    names: ModuleScope<'a>,
 }
 
 #[derive(Debug, Clone)]
 struct ElmEnumVariant {
     name: Identifier,
-    fields: Vec<ElmType>,
+    /// The tag in JSON, after applying serde renames.
+    serialized_name: String,
+    /// Other tags that are accepted when decoding, from `#[serde(alias = "...")]`.
+    aliases: Vec<String>,
+    /// `#[serde(other)]` decodes all unknown tags as this unit variant.
+    other: bool,
+    /// The value of `Red = 1`, counting up from the previous variant when it
+    /// is left out. Only set for enums with an integer representation.
+    discriminant: Option<i64>,
+    fields: VariantFields,
+}
+
+#[derive(Debug, Clone)]
+enum VariantFields {
+    /// `Circle(f64)` or `Point(i32, i32)`. Unit variants have no fields.
+    Unnamed(Vec<ElmType>),
+    /// `Moved { x: i32, y: i32 }` carries a record in elm.
+    Named(Vec<ElmField>),
+}
+
+impl VariantFields {
+    fn types(&self) -> Vec<&ElmType> {
+        match self {
+            VariantFields::Unnamed(fields) => fields.iter().collect(),
+            VariantFields::Named(fields) => fields.iter().map(|field| &field.ty).collect(),
+        }
+    }
+
+    fn types_mut(&mut self) -> Vec<&mut ElmType> {
+        match self {
+            VariantFields::Unnamed(fields) => fields.iter_mut().collect(),
+            VariantFields::Named(fields) => fields.iter_mut().map(|field| &mut field.ty).collect(),
+        }
+    }
 }
 
 const INT_IDENTIFIERS: [&str; 10] = [
     "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize",
 ];
 
+const FLOAT_IDENTIFIERS: [&str; 2] = ["f32", "f64"];
+
+/// Smart pointers that serde treats as the type they point to.
+const TRANSPARENT_IDENTIFIERS: [&str; 4] = ["Box", "Rc", "Arc", "Cow"];
+
+/// Collections that serde writes as JSON arrays.
+const LIST_IDENTIFIERS: [&str; 5] = ["Vec", "VecDeque", "LinkedList", "HashSet", "BTreeSet"];
+
 impl ElmType {
     #[must_use]
     pub fn type_ref(&self) -> String {
         match self {
             ElmType::Int => "Int".to_string(),
+            ElmType::Float => "Float".to_string(),
             ElmType::String => "String".to_string(),
             ElmType::Bool => "Bool".to_string(),
-            ElmType::List(t) => format!("(List {})", t.type_ref()),
+            ElmType::List(t) | ElmType::Array(t, _) => format!("(List {})", t.type_ref()),
             ElmType::Maybe(t) => format!("(Maybe {})", t.type_ref()),
-            ElmType::NamedType(name) => name.0.clone(),
+            ElmType::Result(ok, err) => {
+                format!("(Result.Result {} {})", err.type_ref(), ok.type_ref())
+            }
+            ElmType::Unit => "()".to_string(),
+            ElmType::Tuple(elements) if helpers::is_tuple_record(elements.len()) => {
+                let elements: Vec<String> = elements.iter().map(ElmType::type_ref).collect();
+                format!("(Tuple{} {})", elements.len(), elements.join(" "))
+            }
+            ElmType::Tuple(elements) => {
+                let elements: Vec<String> = elements.iter().map(ElmType::type_ref).collect();
+                format!("( {} )", elements.join(", "))
+            }
+            ElmType::Dict(k, v) if k.is_comparable_key() => {
+                format!("(Dict {} {})", k.type_ref(), v.type_ref())
+            }
+            ElmType::Dict(k, v) => format!("(List ( {}, {} ))", k.type_ref(), v.type_ref()),
+            ElmType::NamedType(name, arguments) if arguments.is_empty() => name.0.clone(),
+            ElmType::NamedType(name, arguments) => {
+                let arguments: Vec<String> = arguments.iter().map(ElmType::type_ref).collect();
+                format!("({} {})", name.0, arguments.join(" "))
+            }
+            ElmType::TypeVariable(name) => type_variable(name),
         }
     }
 
@@ -83,11 +403,47 @@ impl ElmType {
     pub fn decoder_ref(&self) -> String {
         match self {
             ElmType::Int => "Json.Decode.int".to_string(),
+            ElmType::Float => "Json.Decode.float".to_string(),
             ElmType::String => "Json.Decode.string".to_string(),
             ElmType::Bool => "Json.Decode.bool".to_string(),
             ElmType::List(t) => format!("(Json.Decode.list {})", t.decoder_ref()),
+            ElmType::Array(t, length) => {
+                format!("(decodeListOfLength {} {})", length, t.decoder_ref())
+            }
             ElmType::Maybe(t) => format!("(Json.Decode.nullable {})", t.decoder_ref()),
-            ElmType::NamedType(name) => format!("decode{}", name.0),
+            ElmType::Result(ok, err) => {
+                format!(
+                    "(decodeStdResult {} {})",
+                    err.decoder_ref(),
+                    ok.decoder_ref()
+                )
+            }
+            ElmType::Unit => "(Json.Decode.null ())".to_string(),
+            ElmType::Tuple(elements) => {
+                let elements: Vec<String> = elements.iter().map(ElmType::decoder_ref).collect();
+                format!("(decodeTuple{} {})", elements.len(), elements.join(" "))
+            }
+            ElmType::Dict(k, v) => match k.as_ref() {
+                ElmType::String => format!("(Json.Decode.dict {})", v.decoder_ref()),
+                k if k.is_comparable_key() => format!(
+                    "(decodeDictWithKeys {} {})",
+                    k.key_from_string_ref(),
+                    v.decoder_ref()
+                ),
+                k => format!(
+                    "(decodeKeyValueList {} {})",
+                    k.key_from_string_ref(),
+                    v.decoder_ref()
+                ),
+            },
+            ElmType::NamedType(name, arguments) if arguments.is_empty() => {
+                name.function_name("decode")
+            }
+            ElmType::NamedType(name, arguments) => {
+                let arguments: Vec<String> = arguments.iter().map(ElmType::decoder_ref).collect();
+                format!("({} {})", name.function_name("decode"), arguments.join(" "))
+            }
+            ElmType::TypeVariable(name) => format!("decode{}", name.0),
         }
     }
 
@@ -95,67 +451,505 @@ impl ElmType {
     pub fn encoder_ref(&self) -> String {
         match self {
             ElmType::Int => "Json.Encode.int".to_string(),
+            ElmType::Float => "Json.Encode.float".to_string(),
             ElmType::String => "Json.Encode.string".to_string(),
             ElmType::Bool => "Json.Encode.bool".to_string(),
-            ElmType::List(t) => format!("(Json.Encode.list {})", t.encoder_ref()),
+            ElmType::List(t) | ElmType::Array(t, _) => {
+                format!("(Json.Encode.list {})", t.encoder_ref())
+            }
             // Needs a method "Maybe a -> Value" and has access to a method "inner: a -> Value".
             // (Maybe.map inner >> Maybe.withDefault Json.Encode.null)
             ElmType::Maybe(t) => format!(
                 "(Maybe.map {} >> Maybe.withDefault Json.Encode.null)",
                 t.encoder_ref()
             ),
-            ElmType::NamedType(name) => format!("encode{}", name.0),
+            ElmType::Result(ok, err) => {
+                format!(
+                    "(encodeStdResult {} {})",
+                    err.encoder_ref(),
+                    ok.encoder_ref()
+                )
+            }
+            ElmType::Unit => "(always Json.Encode.null)".to_string(),
+            ElmType::Tuple(elements) => {
+                let elements: Vec<String> = elements.iter().map(ElmType::encoder_ref).collect();
+                format!("(encodeTuple{} {})", elements.len(), elements.join(" "))
+            }
+            ElmType::Dict(k, v) if k.is_comparable_key() => format!(
+                "(Json.Encode.dict {} {})",
+                k.key_to_string_ref(),
+                v.encoder_ref()
+            ),
+            ElmType::Dict(k, v) => format!(
+                "(encodeKeyValueList {} {})",
+                k.key_to_string_ref(),
+                v.encoder_ref()
+            ),
+            ElmType::NamedType(name, arguments) if arguments.is_empty() => {
+                name.function_name("encode")
+            }
+            ElmType::NamedType(name, arguments) => {
+                let arguments: Vec<String> = arguments.iter().map(ElmType::encoder_ref).collect();
+                format!("({} {})", name.function_name("encode"), arguments.join(" "))
+            }
+            ElmType::TypeVariable(name) => format!("encode{}", name.0),
+        }
+    }
+
+    /// Whether elm can use this type as a `Dict` key.
+    fn is_comparable_key(&self) -> bool {
+        matches!(self, ElmType::String | ElmType::Int)
+    }
+
+    /// Whether serde_json can write this type as a map key.
+    fn is_valid_map_key(&self) -> bool {
+        self.is_comparable_key()
+            || matches!(
+                self,
+                ElmType::Bool | ElmType::NamedType(_, _) | ElmType::TypeVariable(_)
+            )
+    }
+
+    /// Function that parses a map key, e.g. `String.toInt`.
+    fn key_from_string_ref(&self) -> String {
+        match self {
+            ElmType::String => "Just".to_string(),
+            ElmType::Int => "String.toInt".to_string(),
+            ElmType::Bool => "boolKeyFromString".to_string(),
+            _ => format!("(keyFromStringWith {})", self.decoder_ref()),
+        }
+    }
+
+    /// Function that turns a map key into a string, e.g. `String.fromInt`.
+    fn key_to_string_ref(&self) -> String {
+        match self {
+            ElmType::String => "identity".to_string(),
+            ElmType::Int => "String.fromInt".to_string(),
+            ElmType::Bool => "boolKeyToString".to_string(),
+            _ => format!("(keyToStringWith {})", self.encoder_ref()),
+        }
+    }
+
+    /// Applies the options to a type that was described at runtime, which
+    /// always has strict floats and checked array lengths.
+    fn with_options(self, options: &TypeGenOptions) -> ElmType {
+        let with_options = |ty: Box<ElmType>| Box::new(ty.with_options(options));
+        match self {
+            ElmType::Float if options.float_mode == FloatMode::Lenient => {
+                ElmType::Maybe(Box::new(ElmType::Float))
+            }
+            ElmType::Array(t, length) if options.check_array_length => {
+                ElmType::Array(with_options(t), length)
+            }
+            ElmType::Array(t, _) | ElmType::List(t) => ElmType::List(with_options(t)),
+            ElmType::Maybe(t) => {
+                let inner = t.with_options(options);
+                if options.float_mode == FloatMode::Lenient && inner.is_lenient_float() {
+                    // Like `Option<f64>`, see `from_identifier`.
+                    inner
+                } else {
+                    ElmType::Maybe(Box::new(inner))
+                }
+            }
+            ElmType::Result(ok, err) => ElmType::Result(with_options(ok), with_options(err)),
+            ElmType::Tuple(elements) => ElmType::Tuple(
+                elements
+                    .into_iter()
+                    .map(|ty| ty.with_options(options))
+                    .collect(),
+            ),
+            ElmType::Dict(key, value) => ElmType::Dict(with_options(key), with_options(value)),
+            ElmType::NamedType(name, arguments) => ElmType::NamedType(
+                name,
+                arguments
+                    .into_iter()
+                    .map(|ty| ty.with_options(options))
+                    .collect(),
+            ),
+            ty => ty,
+        }
+    }
+
+    fn is_lenient_float(&self) -> bool {
+        matches!(self, ElmType::Maybe(t) if matches!(**t, ElmType::Float))
+    }
+
+    /// Calls the visitor on this type and on all types nested inside of it.
+    fn walk(&self, visitor: &mut impl FnMut(&ElmType)) {
+        visitor(self);
+        match self {
+            ElmType::Int
+            | ElmType::Float
+            | ElmType::String
+            | ElmType::Bool
+            | ElmType::Unit
+            | ElmType::TypeVariable(_) => {}
+            ElmType::List(t) | ElmType::Array(t, _) | ElmType::Maybe(t) => t.walk(visitor),
+            ElmType::Tuple(elements) | ElmType::NamedType(_, elements) => {
+                for element in elements {
+                    element.walk(visitor);
+                }
+            }
+            ElmType::Dict(k, v) => {
+                k.walk(visitor);
+                v.walk(visitor);
+            }
+            ElmType::Result(ok, err) => {
+                ok.walk(visitor);
+                err.walk(visitor);
+            }
+        }
+    }
+
+    /// Like `walk`, but the visitor may change the types.
+    fn walk_mut(&mut self, visitor: &mut impl FnMut(&mut ElmType)) {
+        visitor(self);
+        match self {
+            ElmType::Int
+            | ElmType::Float
+            | ElmType::String
+            | ElmType::Bool
+            | ElmType::Unit
+            | ElmType::TypeVariable(_) => {}
+            ElmType::List(t) | ElmType::Array(t, _) | ElmType::Maybe(t) => t.walk_mut(visitor),
+            ElmType::Tuple(elements) | ElmType::NamedType(_, elements) => {
+                for element in elements {
+                    element.walk_mut(visitor);
+                }
+            }
+            ElmType::Dict(k, v) => {
+                k.walk_mut(visitor);
+                v.walk_mut(visitor);
+            }
+            ElmType::Result(ok, err) => {
+                ok.walk_mut(visitor);
+                err.walk_mut(visitor);
+            }
+        }
+    }
+
+    /// Collects the names of all types this type refers to, e.g. `Card` for
+    /// `Vec<Card>`. These need to be exported as well.
+    fn collect_named_types(&self, output: &mut Vec<Identifier>) {
+        self.walk(&mut |ty| {
+            if let ElmType::NamedType(name, _) = ty {
+                output.push(name.clone());
+            }
+        });
+    }
+
+    fn uses_dict(&self) -> bool {
+        let mut uses_dict = false;
+        self.walk(&mut |ty| {
+            uses_dict |= matches!(ty, ElmType::Dict(k, _) if k.is_comparable_key());
+        });
+        uses_dict
+    }
+
+    /// The elm value for a missing field of this type, mirroring rust's
+    /// `Default`. `None` when we don't know the default.
+    fn default_value(&self) -> Option<String> {
+        match self {
+            ElmType::Int | ElmType::Float => Some("0".to_string()),
+            ElmType::String => Some("\"\"".to_string()),
+            ElmType::Bool => Some("False".to_string()),
+            ElmType::List(_) | ElmType::Array(_, _) => Some("[]".to_string()),
+            ElmType::Maybe(_) => Some("Nothing".to_string()),
+            ElmType::Unit => Some("()".to_string()),
+            ElmType::Dict(key, _) if key.is_comparable_key() => Some("Dict.empty".to_string()),
+            ElmType::Dict(_, _) => Some("[]".to_string()),
+            ElmType::Tuple(elements) if !helpers::is_tuple_record(elements.len()) => {
+                let defaults: Option<Vec<String>> =
+                    elements.iter().map(ElmType::default_value).collect();
+                Some(format!("( {} )", defaults?.join(", ")))
+            }
+            ElmType::Tuple(_)
+            | ElmType::Result(_, _)
+            | ElmType::NamedType(_, _)
+            | ElmType::TypeVariable(_) => None,
         }
     }
 
-    fn from_identifier(type_path: &TypePath) -> Result<Self> {
-        let identifier = simple_path(&type_path.path)?;
+    /// Whether this may be a JSON object. Named types could also be enums
+    /// or newtypes, we give them the benefit of the doubt.
+    fn is_object(&self) -> bool {
+        matches!(
+            self,
+            ElmType::Dict(_, _) | ElmType::NamedType(_, _) | ElmType::TypeVariable(_)
+        )
+    }
+
+    /// Collects the helper functions the decoder and encoder of this type call.
+    fn collect_helpers(&self, output: &mut BTreeSet<ElmHelper>) {
+        self.walk(&mut |ty| {
+            if let ElmType::Tuple(elements) = ty {
+                output.insert(ElmHelper::Tuple(elements.len()));
+            }
+            if let ElmType::Array(_, _) = ty {
+                output.insert(ElmHelper::DecodeListOfLength);
+            }
+            if let ElmType::Result(_, _) = ty {
+                output.insert(ElmHelper::Result);
+            }
+            if let ElmType::Dict(k, _) = ty {
+                match k.as_ref() {
+                    ElmType::String => {}
+                    ElmType::Int => {
+                        output.insert(ElmHelper::DecodeDictWithKeys);
+                    }
+                    ElmType::Bool => {
+                        output.insert(ElmHelper::DecodeKeyValueList);
+                        output.insert(ElmHelper::EncodeKeyValueList);
+                        output.insert(ElmHelper::BoolKeyFromString);
+                        output.insert(ElmHelper::BoolKeyToString);
+                    }
+                    _ => {
+                        output.insert(ElmHelper::DecodeKeyValueList);
+                        output.insert(ElmHelper::EncodeKeyValueList);
+                        output.insert(ElmHelper::KeyFromStringWith);
+                        output.insert(ElmHelper::KeyToStringWith);
+                    }
+                }
+            }
+        });
+    }
+
+    fn from_identifier(type_path: &TypePath, scope: &Scope) -> Result<Self> {
+        if let Some(described) = scope.names.described_type(&type_path.path) {
+            return Ok(described.clone().with_options(scope.options));
+        }
+        if let Some(ident) = type_path.path.get_ident() {
+            let identifier = Identifier(ident.to_string());
+            if scope.generics.contains(&identifier) {
+                return Ok(ElmType::TypeVariable(identifier));
+            }
+        }
+        // Types of the crate win over builtins with the same name.
+        let identifier = match scope.names.resolve(&type_path.path) {
+            ResolvedPath::Definition(identifier) => {
+                let arguments = extract_inner_types(type_path, scope)?;
+                return Ok(ElmType::NamedType(identifier, arguments));
+            }
+            ResolvedPath::Name(identifier) => identifier,
+        };
         if INT_IDENTIFIERS.iter().any(|s| *s == identifier.0) {
             Ok(ElmType::Int)
-        } else if identifier.0 == "String" {
+        } else if FLOAT_IDENTIFIERS.iter().any(|s| *s == identifier.0) {
+            match scope.options.float_mode {
+                FloatMode::Strict => Ok(ElmType::Float),
+                FloatMode::Lenient => Ok(ElmType::Maybe(Box::new(ElmType::Float))),
+            }
+        } else if identifier.0 == "String" || identifier.0 == "str" {
             Ok(ElmType::String)
         } else if identifier.0 == "bool" {
             Ok(ElmType::Bool)
-        } else if identifier.0 == "Vec" {
-            Ok(ElmType::List(Box::new(extract_one_inner_type(type_path)?)))
+        } else if LIST_IDENTIFIERS.iter().any(|s| *s == identifier.0) {
+            Ok(ElmType::List(Box::new(extract_one_inner_type(
+                type_path, scope,
+            )?)))
+        } else if TRANSPARENT_IDENTIFIERS.iter().any(|s| *s == identifier.0) {
+            extract_one_inner_type(type_path, scope)
+        } else if identifier.0 == "HashMap" || identifier.0 == "BTreeMap" {
+            let (key, value) = extract_two_inner_types(type_path, scope)?;
+            if !key.is_valid_map_key() {
+                return Err(syn::Error::new(
+                    type_path.span(),
+                    "Map keys must serialize as strings, integers, bools or enum variants",
+                )
+                .into());
+            }
+            Ok(ElmType::Dict(Box::new(key), Box::new(value)))
+        } else if identifier.0 == "Result" {
+            let mut inner_types = extract_inner_types(type_path, scope)?;
+            if inner_types.len() != 2 {
+                // Like anyhow::Result<T> or io::Result<T>.
+                let message = format!(
+                    "{} is an alias for a Result with a fixed error type, which is not supported",
+                    path_to_string(&type_path.path)
+                );
+                return Err(syn::Error::new_spanned(type_path, message).into());
+            }
+            let err = inner_types.pop().unwrap();
+            let ok = inner_types.pop().unwrap();
+            Ok(ElmType::Result(Box::new(ok), Box::new(err)))
         } else if identifier.0 == "Option" {
-            Ok(ElmType::Maybe(Box::new(extract_one_inner_type(type_path)?)))
+            let inner = extract_one_inner_type(type_path, scope)?;
+            if scope.options.float_mode == FloatMode::Lenient && inner.is_lenient_float() {
+                // Option<f64> can't be told apart from a NaN, both are null.
+                Ok(inner)
+            } else {
+                Ok(ElmType::Maybe(Box::new(inner)))
+            }
         } else {
-            Ok(ElmType::NamedType(identifier))
+            let arguments = extract_inner_types(type_path, scope)?;
+            Ok(ElmType::NamedType(identifier, arguments))
         }
     }
 }
 
-fn extract_one_inner_type(type_path: &TypePath) -> Result<ElmType> {
-    let inner_type = if let syn::PathArguments::AngleBracketed(arguments) =
-        &type_path.path.segments.last().unwrap().arguments
-    {
-        let generic = arguments.args.first().expect("Type has no first argument");
-        let type_ref = match generic {
-            syn::GenericArgument::Type(type_path) => elm_type_from_type(type_path)?,
-            _ => panic!("Type has non-type argument"),
-        };
-        type_ref
-    } else {
-        panic!("Vec has no angle brackets")
-    };
-    Ok(inner_type)
+fn extract_one_inner_type(type_path: &TypePath, scope: &Scope) -> Result<ElmType> {
+    let mut inner_types = extract_inner_types(type_path, scope)?;
+    if inner_types.is_empty() {
+        let message = format!("{} needs a type argument", last_path(&type_path.path)?.0);
+        return Err(syn::Error::new_spanned(type_path, message).into());
+    }
+    Ok(inner_types.swap_remove(0))
+}
+
+fn extract_two_inner_types(type_path: &TypePath, scope: &Scope) -> Result<(ElmType, ElmType)> {
+    let mut inner_types = extract_inner_types(type_path, scope)?.into_iter();
+    match (inner_types.next(), inner_types.next()) {
+        (Some(first), Some(second)) => Ok((first, second)),
+        _ => {
+            let message = format!("{} needs two type arguments", last_path(&type_path.path)?.0);
+            Err(syn::Error::new_spanned(type_path, message).into())
+        }
+    }
+}
+
+fn extract_inner_types(type_path: &TypePath, scope: &Scope) -> Result<Vec<ElmType>> {
+    match &type_path.path.segments.last().unwrap().arguments {
+        syn::PathArguments::AngleBracketed(arguments) => {
+            let mut inner_types = vec![];
+            for generic in &arguments.args {
+                match generic {
+                    syn::GenericArgument::Type(type_path) => {
+                        inner_types.push(elm_type_from_type(type_path, scope)?)
+                    }
+                    // Lifetimes like in Cow<'a, str> don't matter for serialization.
+                    syn::GenericArgument::Lifetime(_) => {}
+                    _ => {
+                        let message = "Only types and lifetimes are supported as arguments";
+                        return Err(syn::Error::new_spanned(generic, message).into());
+                    }
+                }
+            }
+            Ok(inner_types)
+        }
+        syn::PathArguments::None => Ok(vec![]),
+        syn::PathArguments::Parenthesized(arguments) => {
+            let message = "Parenthesized arguments are not supported";
+            Err(syn::Error::new_spanned(arguments, message).into())
+        }
+    }
+}
+
+/// Elm type variables start with a lowercase letter: `T` becomes `t`.
+fn type_variable(name: &Identifier) -> String {
+    let mut chars = name.0.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// An elm list of strings on a single line, e.g. `[ "id", "name" ]`.
+fn string_list(strings: &[String]) -> String {
+    if strings.is_empty() {
+        return "[]".to_string();
+    }
+    let quoted: Vec<String> = strings.iter().map(|s| format!("\"{}\"", s)).collect();
+    format!("[ {} ]", quoted.join(", "))
 }
 
-fn simple_path(path: &syn::Path) -> Result<Identifier> {
-    if path.segments.len() != 1 {
-        return Err(syn::Error::new(path.span(), "Only simple paths are supported").into());
+/// A record type on a single line, e.g. `{ x : Int, y : Int }`.
+fn record_type(fields: &[ElmField]) -> String {
+    if fields.is_empty() {
+        return "{}".to_string();
     }
-    Ok(Identifier(path.segments.first().unwrap().ident.to_string()))
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| format!("{} : {}", field.elm_name(), field.ty.type_ref()))
+        .collect();
+    format!("{{ {} }}", fields.join(", "))
+}
+
+/// Function that builds a variant carrying a record from its fields, e.g.
+/// `(\x y -> Moved { x = x, y = y })`.
+fn record_constructor(variant: &Identifier, fields: &[ElmField]) -> String {
+    if fields.is_empty() {
+        return format!("({} {{}})", variant.0);
+    }
+    let names: Vec<String> = fields.iter().map(ElmField::elm_name).collect();
+    let assignments: Vec<String> = names
+        .iter()
+        .map(|name| format!("{} = {}", name, name))
+        .collect();
+    format!(
+        "(\\{} -> {} {{ {} }})",
+        names.join(" "),
+        variant.0,
+        assignments.join(", ")
+    )
+}
+
+/// The type name followed by its type variables, e.g. `Page t`.
+fn declared_type(name: &Identifier, generics: &[Identifier]) -> String {
+    let mut output = name.0.clone();
+    for generic in generics {
+        output.push(' ');
+        output.push_str(&type_variable(generic));
+    }
+    output
+}
+
+/// The type applied to its type variables, e.g. `Page` or `(Page t)`.
+fn applied_type(name: &Identifier, generics: &[Identifier]) -> String {
+    if generics.is_empty() {
+        name.0.clone()
+    } else {
+        let variables: Vec<String> = generics.iter().map(type_variable).collect();
+        format!("({} {})", name.0, variables.join(" "))
+    }
+}
+
+/// Generic types take a decoder for each type variable:
+/// `Json.Decode.Decoder t -> ` for `Page<T>`.
+fn decoder_parameter_types(generics: &[Identifier]) -> String {
+    generics
+        .iter()
+        .map(|generic| format!("Json.Decode.Decoder {} -> ", type_variable(generic)))
+        .collect()
+}
+
+/// Generic types take an encoder for each type variable:
+/// `(t -> Json.Encode.Value) -> ` for `Page<T>`.
+fn encoder_parameter_types(generics: &[Identifier]) -> String {
+    generics
+        .iter()
+        .map(|generic| format!("({} -> Json.Encode.Value) -> ", type_variable(generic)))
+        .collect()
+}
+
+/// Names of the decoder parameters, e.g. ` decodeT` for `Page<T>`.
+fn decoder_parameters(generics: &[Identifier]) -> String {
+    generics
+        .iter()
+        .map(|generic| format!(" decode{}", generic.0))
+        .collect()
+}
+
+/// Names of the encoder parameters, e.g. ` encodeT` for `Page<T>`.
+fn encoder_parameters(generics: &[Identifier]) -> String {
+    generics
+        .iter()
+        .map(|generic| format!(" encode{}", generic.0))
+        .collect()
 }
 
 impl ElmFile {
     pub fn generate_file_content(&self) -> String {
         let mut result = String::new();
         result.push_str(&format!("module {} exposing (..)\n\n\n", self.name));
+        if self.field_types().any(ElmType::uses_dict) {
+            result.push_str("import Dict exposing (Dict)\n");
+        }
         result.push_str("import Json.Decode\n");
         result.push_str("import Json.Encode\n");
         result.push_str("import Json.Decode.Pipeline\n");
+        for module in self.imported_modules() {
+            result.push_str(&format!("import {}\n", module));
+        }
         for struct_ in &self.structs {
             result.push('\n');
             result.push_str(&struct_.type_def());
@@ -172,39 +966,143 @@ impl ElmFile {
             result.push('\n');
             result.push_str(&enum_.decoder_def());
         }
+        for helper in self.helpers() {
+            result.push('\n');
+            result.push_str(&helper.definition());
+        }
         result
     }
+
+    /// All helper functions used by the types in this file, including the
+    /// helpers those helpers call.
+    fn helpers(&self) -> BTreeSet<ElmHelper> {
+        let mut helpers = BTreeSet::new();
+        for ty in self.field_types() {
+            ty.collect_helpers(&mut helpers);
+        }
+        for struct_ in &self.structs {
+            struct_.collect_helpers(&mut helpers);
+        }
+        for enum_ in &self.enums {
+            enum_.collect_helpers(&mut helpers);
+        }
+        let mut queue: Vec<ElmHelper> = helpers.iter().copied().collect();
+        while let Some(helper) = queue.pop() {
+            for dependency in helper.dependencies() {
+                if helpers.insert(*dependency) {
+                    queue.push(*dependency);
+                }
+            }
+        }
+        helpers
+    }
+
+    fn field_types(&self) -> impl Iterator<Item = &ElmType> {
+        let struct_types = self.structs.iter().flat_map(ElmStruct::field_types);
+        let enum_types = self.enums.iter().flat_map(ElmEnum::field_types);
+        struct_types.chain(enum_types)
+    }
 }
 
 impl ElmStruct {
+    fn collect_helpers(&self, output: &mut BTreeSet<ElmHelper>) {
+        if let StructFields::Named(fields) = &self.fields {
+            for field in fields {
+                field.collect_helpers(output);
+            }
+        }
+    }
+
+    fn field_types(&self) -> Vec<&ElmType> {
+        match &self.fields {
+            StructFields::Named(fields) => fields.iter().map(|field| &field.ty).collect(),
+            StructFields::Newtype { inner, .. } => vec![inner],
+            StructFields::Tuple(fields) => fields.iter().collect(),
+            StructFields::Unit => vec![],
+        }
+    }
+
+    fn field_types_mut(&mut self) -> Vec<&mut ElmType> {
+        match &mut self.fields {
+            StructFields::Named(fields) => fields.iter_mut().map(|field| &mut field.ty).collect(),
+            StructFields::Newtype { inner, .. } => vec![inner],
+            StructFields::Tuple(fields) => fields.iter_mut().collect(),
+            StructFields::Unit => vec![],
+        }
+    }
+
+    fn referenced_types(&self) -> Vec<Identifier> {
+        let mut output = vec![];
+        for ty in self.field_types() {
+            ty.collect_named_types(&mut output);
+        }
+        output
+    }
+
     #[must_use]
     pub fn type_ref(&self) -> String {
         self.name.0.clone()
     }
 
+    /// The name with its type variables, e.g. `Page t`.
+    fn declared_type(&self) -> String {
+        declared_type(&self.name, &self.generics)
+    }
+
     #[must_use]
     pub fn type_def(&self) -> String {
-        // Outputs something like:
-        // type alias Person =
-        //     { age : Int
-        //     , surname : String
-        //     }
-        let mut output = format!("type alias {} =\n", self.name.0);
-        let mut is_first = true;
-        for (field, ty) in &self.fields {
-            if is_first {
-                output.push_str("    { ");
-                is_first = false;
-            } else {
-                output.push_str("    , ");
+        match &self.fields {
+            StructFields::Named(fields) => {
+                // Outputs something like:
+                // type alias Person =
+                //     { age : Int
+                //     , surname : String
+                //     }
+                let mut output = format!("type alias {} =\n", self.declared_type());
+                let mut is_first = true;
+                for field in fields {
+                    if is_first {
+                        output.push_str("    { ");
+                        is_first = false;
+                    } else {
+                        output.push_str("    , ");
+                    }
+                    output.push_str(&format!("{} : {}\n", field.elm_name(), field.ty.type_ref()));
+                }
+                output.push_str("    }\n");
+                output
             }
-            output.push_str(&format!(
-                "{} : {}\n",
-                field.camel_case(LowerCase),
-                ty.type_ref()
-            ));
+            StructFields::Newtype {
+                inner,
+                opaque: false,
+            } => {
+                format!(
+                    "type alias {} =\n    {}\n",
+                    self.declared_type(),
+                    inner.type_ref()
+                )
+            }
+            StructFields::Newtype {
+                inner,
+                opaque: true,
+            } => self.custom_type_def(&[inner]),
+            StructFields::Tuple(fields) => {
+                let fields: Vec<&ElmType> = fields.iter().collect();
+                self.custom_type_def(&fields)
+            }
+            StructFields::Unit => self.custom_type_def(&[]),
+        }
+    }
+
+    /// A custom type with a single constructor of the same name:
+    /// `type Point = Point Int Int`
+    fn custom_type_def(&self, fields: &[&ElmType]) -> String {
+        let mut output = format!("type {}\n    = {}", self.declared_type(), self.name.0);
+        for field in fields {
+            output.push(' ');
+            output.push_str(&field.type_ref());
         }
-        output.push_str("    }\n");
+        output.push('\n');
         output
     }
 
@@ -223,26 +1121,61 @@ impl ElmStruct {
         //         |> Json.Decode.Pipeline.required "age" Json.Decode.int
         //         |> Json.Decode.Pipeline.required "surname" Json.Decode.string
         let mut output = format!(
-            "decode{} : Json.Decode.Decoder {}\n",
-            self.name.0, self.name.0
+            "decode{} : {}Json.Decode.Decoder {}\n",
+            self.name.0,
+            decoder_parameter_types(&self.generics),
+            applied_type(&self.name, &self.generics)
         );
-        output.push_str(&format!("decode{} =\n", self.name.0));
-        output.push_str(&format!("    Json.Decode.succeed {}\n", self.name.0));
-        for (field, ty) in &self.fields {
-            output.push_str(&format!(
-                "        |> Json.Decode.Pipeline.required \"{}\" {}\n",
-                field.0,
-                ty.decoder_ref()
-            ));
-        }
-        output
-    }
-
-    #[must_use]
-    pub fn encoder_ref(&self) -> String {
-        format!("encode{}", self.name.0)
-    }
-
+        output.push_str(&format!(
+            "decode{}{} =\n",
+            self.name.0,
+            decoder_parameters(&self.generics)
+        ));
+        match &self.fields {
+            StructFields::Named(fields) => {
+                output.push_str(&format!("    Json.Decode.succeed {}\n", self.name.0));
+                for field in fields {
+                    output.push_str(&format!("        |> {}\n", field.pipeline_decoder()));
+                }
+            }
+            StructFields::Newtype {
+                inner,
+                opaque: false,
+            } => {
+                output.push_str(&format!("    {}\n", inner.decoder_ref()));
+            }
+            StructFields::Newtype {
+                inner,
+                opaque: true,
+            } => {
+                output.push_str(&format!(
+                    "    Json.Decode.map {} {}\n",
+                    self.name.0,
+                    inner.decoder_ref()
+                ));
+            }
+            StructFields::Tuple(fields) => {
+                output.push_str(&format!("    Json.Decode.succeed {}\n", self.name.0));
+                for (i, ty) in fields.iter().enumerate() {
+                    output.push_str(&format!(
+                        "        |> Json.Decode.Pipeline.custom (Json.Decode.index {} {})\n",
+                        i,
+                        ty.decoder_ref()
+                    ));
+                }
+            }
+            StructFields::Unit => {
+                output.push_str(&format!("    Json.Decode.null {}\n", self.name.0));
+            }
+        }
+        output
+    }
+
+    #[must_use]
+    pub fn encoder_ref(&self) -> String {
+        format!("encode{}", self.name.0)
+    }
+
     #[must_use]
     pub fn encoder_def(&self) -> String {
         // Outputs something like:
@@ -253,34 +1186,92 @@ impl ElmStruct {
         //         , ( "surname", Json.Encode.string person.surname )
         //         ]
         let mut output = format!(
-            "encode{} : {} -> Json.Encode.Value\n",
-            self.name.0, self.name.0
+            "encode{} : {}{} -> Json.Encode.Value\n",
+            self.name.0,
+            encoder_parameter_types(&self.generics),
+            self.declared_type()
         );
         let this = self.name.0.to_lowercase();
-        output.push_str(&format!("encode{} {} =\n", self.name.0, this));
-        output.push_str("    Json.Encode.object\n");
-        let mut is_first = true;
-        for (field, ty) in &self.fields {
-            if is_first {
-                output.push_str("        [ ");
-                is_first = false;
-            } else {
-                output.push_str("        , ");
+        let pattern = match &self.fields {
+            StructFields::Named(_) | StructFields::Newtype { opaque: false, .. } => this.clone(),
+            StructFields::Newtype { opaque: true, .. } => format!("({} x)", self.name.0),
+            StructFields::Tuple(fields) => {
+                let mut pattern = format!("({}", self.name.0);
+                for i in 0..fields.len() {
+                    pattern.push_str(&format!(" x{}", i));
+                }
+                pattern.push(')');
+                pattern
             }
-            output.push_str(&format!(
-                "( \"{}\", {} {}.{} )\n",
-                field.0,
-                ty.encoder_ref(),
-                this,
-                field.camel_case(LowerCase)
-            ));
+            StructFields::Unit => "_".to_string(),
+        };
+        output.push_str(&format!(
+            "encode{}{} {} =\n",
+            self.name.0,
+            encoder_parameters(&self.generics),
+            pattern
+        ));
+        match &self.fields {
+            StructFields::Named(fields) => {
+                output.push_str(&indent_lines(&object_encoder(fields, &this, None), 4));
+            }
+            StructFields::Newtype {
+                inner,
+                opaque: false,
+            } => {
+                output.push_str(&format!("    {} {}\n", inner.encoder_ref(), this));
+            }
+            StructFields::Newtype {
+                inner,
+                opaque: true,
+            } => {
+                output.push_str(&format!("    {} x\n", inner.encoder_ref()));
+            }
+            StructFields::Tuple(fields) => {
+                output.push_str("    Json.Encode.list identity\n");
+                if fields.is_empty() {
+                    // `struct Empty();` is written as an empty array.
+                    output.push_str("        []\n");
+                } else {
+                    for (i, ty) in fields.iter().enumerate() {
+                        let prefix = if i == 0 { "[" } else { "," };
+                        output.push_str(&format!(
+                            "        {} {} x{}\n",
+                            prefix,
+                            ty.encoder_ref(),
+                            i
+                        ));
+                    }
+                    output.push_str("        ]\n");
+                }
+            }
+            StructFields::Unit => output.push_str("    Json.Encode.null\n"),
         }
-        output.push_str("        ]\n");
         output
     }
 }
 
 impl ElmEnum {
+    fn field_types(&self) -> impl Iterator<Item = &ElmType> {
+        self.variants
+            .iter()
+            .flat_map(|variant| variant.fields.types())
+    }
+
+    fn field_types_mut(&mut self) -> impl Iterator<Item = &mut ElmType> {
+        self.variants
+            .iter_mut()
+            .flat_map(|variant| variant.fields.types_mut())
+    }
+
+    fn referenced_types(&self) -> Vec<Identifier> {
+        let mut output = vec![];
+        for ty in self.field_types() {
+            ty.collect_named_types(&mut output);
+        }
+        output
+    }
+
     pub fn type_def(&self) -> String {
         // Outputs something like:
         // type Message
@@ -288,7 +1279,7 @@ impl ElmEnum {
         //     | SecondaryVariant Int String
         //     | ThirdVariant
 
-        let mut output = format!("type {}\n", self.name.0);
+        let mut output = format!("type {}\n", declared_type(&self.name, &self.generics));
         let mut is_first = true;
         for variant in &self.variants {
             if is_first {
@@ -298,9 +1289,17 @@ impl ElmEnum {
                 output.push_str("    | ");
             }
             output.push_str(&variant.name.0);
-            for field in &variant.fields {
-                output.push(' ');
-                output.push_str(&field.type_ref());
+            match &variant.fields {
+                VariantFields::Unnamed(fields) => {
+                    for field in fields {
+                        output.push(' ');
+                        output.push_str(&field.type_ref());
+                    }
+                }
+                VariantFields::Named(fields) => {
+                    output.push(' ');
+                    output.push_str(&record_type(fields));
+                }
             }
             output.push('\n');
         }
@@ -309,130 +1308,707 @@ impl ElmEnum {
 
     fn encoder_def(&self) -> String {
         let mut output = format!(
-            "encode{} : {} -> Json.Encode.Value\n",
-            self.name.0, self.name.0
+            "encode{} : {}{} -> Json.Encode.Value\n",
+            self.name.0,
+            encoder_parameter_types(&self.generics),
+            declared_type(&self.name, &self.generics)
         );
         let this = self.name.0.to_lowercase();
-        output.push_str(&format!("encode{} {} =\n", self.name.0, this));
+        output.push_str(&format!(
+            "encode{}{} {} =\n",
+            self.name.0,
+            encoder_parameters(&self.generics),
+            this
+        ));
         output.push_str(&format!("    case {} of\n", this));
 
         for variant in &self.variants {
-            if variant.fields.is_empty() {
-                output.push_str(&format!("        {} ->\n", variant.name.0));
-                output.push_str(&format!(
-                    "            Json.Encode.string \"{}\"\n\n",
-                    variant.name.0
-                ));
-            } else if variant.fields.len() == 1 {
-                let field = variant.fields.first().unwrap();
-                output.push_str(&format!("        {} x ->\n", variant.name.0));
-                output.push_str("            Json.Encode.object\n");
-                output.push_str(&format!(
-                    "                [ ( \"{}\", {} x )\n",
-                    variant.name.0,
-                    field.encoder_ref()
-                ));
-                output.push_str("                ]\n\n");
-            } else {
-                output.push_str(&format!("        {} ", variant.name.0));
-                for (i, _) in variant.fields.iter().enumerate() {
-                    output.push_str(&format!("x{} ", i));
-                }
-                output.push_str("->\n");
-                output.push_str("            Json.Encode.object\n");
-                output.push_str(&format!("                [ ( \"{}\"\n", variant.name.0));
-                output.push_str("                  , Json.Encode.list (\\v -> v)\n");
-                let mut is_first = true;
-                for (i, field) in variant.fields.iter().enumerate() {
-                    if is_first {
-                        output.push_str("                        [ ");
-                        is_first = false;
+            output.push_str(&format!("        {} ->\n", variant.case_pattern()));
+            match &self.representation {
+                EnumRepresentation::External => output.push_str(&variant.external_encoder()),
+                EnumRepresentation::Internal { tag } => {
+                    output.push_str(&variant.internal_encoder(tag))
+                }
+                EnumRepresentation::Adjacent { tag, content } => {
+                    output.push_str(&variant.adjacent_encoder(tag, content))
+                }
+                EnumRepresentation::Untagged => {
+                    output.push_str(&indent_lines(&variant.payload_encoder(), 12))
+                }
+                EnumRepresentation::Integer => {
+                    // Negative numbers need parentheses as function arguments.
+                    let value = variant.discriminant.unwrap_or_default();
+                    if value < 0 {
+                        output.push_str(&format!("            Json.Encode.int ({})\n", value));
                     } else {
-                        output.push_str("                        , ");
+                        output.push_str(&format!("            Json.Encode.int {}\n", value));
                     }
-                    output.push_str(&format!("{} x{}\n", field.encoder_ref(), i));
                 }
-                output.push_str("                        ]\n");
-                output.push_str("                  )\n");
-                output.push_str("                ]\n\n");
             }
+            output.push('\n');
         }
 
         output
     }
 
     fn decoder_def(&self) -> String {
-        let mut output = format!(
-            "decode{} : Json.Decode.Decoder {}\n",
-            self.name.0, self.name.0
+        let decoder_type = format!(
+            "{}Json.Decode.Decoder {}",
+            decoder_parameter_types(&self.generics),
+            applied_type(&self.name, &self.generics)
         );
+        let parameters = decoder_parameters(&self.generics);
+        let mut output = format!("decode{} : {}\n", self.name.0, decoder_type);
 
         // General decoder that collects all variant decoders
-        output.push_str(&format!("decode{} =\n", self.name.0));
-        output.push_str("    Json.Decode.oneOf\n");
-        let mut is_first = true;
-        for variant in &self.variants {
-            if is_first {
-                output.push_str("        [ ");
-                is_first = false;
-            } else {
-                output.push_str("        , ");
+        output.push_str(&format!("decode{}{} =\n", self.name.0, parameters));
+        if self.representation == EnumRepresentation::Integer {
+            // The number is all there is, so there are no variant decoders.
+            output.push_str(&self.discriminant_dispatch());
+            return output;
+        }
+        match &self.representation {
+            // Untagged variants are tried in order, like serde does.
+            EnumRepresentation::External | EnumRepresentation::Untagged => {
+                output.push_str("    Json.Decode.oneOf\n");
+                let mut is_first = true;
+                for variant in &self.variants {
+                    if is_first {
+                        output.push_str("        [ ");
+                        is_first = false;
+                    } else {
+                        output.push_str("        , ");
+                    }
+                    output.push_str(&format!(
+                        "decode{}{}{}\n",
+                        self.name.0, variant.name.0, parameters
+                    ));
+                }
+                output.push_str("        ]\n");
             }
-            output.push_str(&format!("decode{}{}\n", self.name.0, variant.name.0));
+            EnumRepresentation::Internal { tag } | EnumRepresentation::Adjacent { tag, .. } => {
+                output.push_str(&self.tag_dispatch(tag, &parameters));
+            }
+            EnumRepresentation::Integer => unreachable!("Integer enums have no variant decoders"),
         }
-        output.push_str("        ]\n");
 
         // Variant decoders
         for variant in &self.variants {
             output.push_str("\n\n");
             output.push_str(&format!(
-                "decode{}{} : Json.Decode.Decoder {}\n",
-                self.name.0, variant.name.0, self.name.0
+                "decode{}{} : {}\n",
+                self.name.0, variant.name.0, decoder_type
+            ));
+            output.push_str(&format!(
+                "decode{}{}{} =\n",
+                self.name.0, variant.name.0, parameters
             ));
-            output.push_str(&format!("decode{}{} =\n", self.name.0, variant.name.0));
-            if variant.fields.is_empty() {
-                // TODO: All those variants should be joined together for performance.
-                output.push_str("    Json.Decode.andThen\n");
-                output.push_str("        (\\str ->\n");
-                output.push_str("            case str of\n");
-                output.push_str(&format!("                \"{}\" ->\n", variant.name.0));
+            match &self.representation {
+                EnumRepresentation::External if variant.other => {
+                    output.push_str(&self.external_other_decoder(variant))
+                }
+                EnumRepresentation::External => output.push_str(&variant.external_decoder()),
+                EnumRepresentation::Internal { .. } => output.push_str(&variant.internal_decoder()),
+                EnumRepresentation::Adjacent { content, .. } => {
+                    output.push_str(&variant.adjacent_decoder(content))
+                }
+                EnumRepresentation::Untagged => {
+                    output.push_str(&indent_lines(&variant.payload_decoder(), 4))
+                }
+                EnumRepresentation::Integer => {
+                    unreachable!("Integer enums have no variant decoders")
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Reads the tag once and continues with the decoder of that variant.
+    fn tag_dispatch(&self, tag: &str, parameters: &str) -> String {
+        // Json.Decode.field "type" Json.Decode.string
+        //     |> Json.Decode.andThen
+        //         (\tag ->
+        //             case tag of
+        //                 "Started" ->
+        //                     decodeEventStarted
+        //
+        //                 _ ->
+        //                     Json.Decode.fail ("Unknown variant " ++ tag)
+        //         )
+        let mut output = format!("    Json.Decode.field \"{}\" Json.Decode.string\n", tag);
+        output.push_str("        |> Json.Decode.andThen\n");
+        output.push_str("            (\\tag ->\n");
+        output.push_str("                case tag of\n");
+        for variant in &self.variants {
+            for tag in variant.tags() {
+                output.push_str(&format!("                    \"{}\" ->\n", tag));
                 output.push_str(&format!(
-                    "                    Json.Decode.succeed {}\n\n",
-                    variant.name.0
+                    "                        decode{}{}{}\n\n",
+                    self.name.0, variant.name.0, parameters
+                ));
+            }
+        }
+        output.push_str("                    _ ->\n");
+        match self.variants.iter().find(|variant| variant.other) {
+            Some(other) => output.push_str(&format!(
+                "                        decode{}{}{}\n",
+                self.name.0, other.name.0, parameters
+            )),
+            None => output.push_str(
+                "                        Json.Decode.fail (\"Unknown variant \" ++ tag)\n",
+            ),
+        }
+        output.push_str("            )\n");
+        output
+    }
+
+    fn discriminant_dispatch(&self) -> String {
+        // Json.Decode.int
+        //     |> Json.Decode.andThen
+        //         (\value ->
+        //             case value of
+        //                 1 ->
+        //                     Json.Decode.succeed Red
+        //
+        //                 _ ->
+        //                     Json.Decode.fail ("Unknown value for Color: " ++ String.fromInt value)
+        //         )
+        let mut output = "    Json.Decode.int\n".to_string();
+        output.push_str("        |> Json.Decode.andThen\n");
+        output.push_str("            (\\value ->\n");
+        output.push_str("                case value of\n");
+        for variant in &self.variants {
+            output.push_str(&format!(
+                "                    {} ->\n",
+                variant.discriminant.unwrap_or_default()
+            ));
+            output.push_str(&format!(
+                "                        Json.Decode.succeed {}\n\n",
+                variant.name.0
+            ));
+        }
+        output.push_str("                    _ ->\n");
+        output.push_str(&format!(
+            "                        Json.Decode.fail (\"Unknown value for {}: \" ++ String.fromInt value)\n",
+            self.name.0
+        ));
+        output.push_str("            )\n");
+        output
+    }
+
+    /// `#[serde(other)]` in an externally tagged enum accepts every string
+    /// that is not the tag of another variant.
+    fn external_other_decoder(&self, other: &ElmEnumVariant) -> String {
+        let known_tags: Vec<String> = self
+            .variants
+            .iter()
+            .filter(|variant| !variant.other)
+            .flat_map(|variant| variant.tags())
+            .collect();
+        let mut output = "    Json.Decode.string\n".to_string();
+        output.push_str("        |> Json.Decode.andThen\n");
+        output.push_str("            (\\str ->\n");
+        output.push_str(&format!(
+            "                if List.member str {} then\n",
+            string_list(&known_tags)
+        ));
+        output
+            .push_str("                    Json.Decode.fail (\"Unexpected variant \" ++ str)\n\n");
+        output.push_str("                else\n");
+        output.push_str(&format!(
+            "                    Json.Decode.succeed {}\n",
+            other.name.0
+        ));
+        output.push_str("            )\n");
+        output
+    }
+
+    /// serde fails at runtime for variants that don't fit the representation,
+    /// so we fail early instead.
+    fn validate_representation(&self) -> Result<()> {
+        for variant in &self.variants {
+            let reason = match &variant.fields {
+                VariantFields::Unnamed(fields)
+                    if self.representation == EnumRepresentation::Integer && fields.is_empty() =>
+                {
+                    continue
+                }
+                _ if self.representation == EnumRepresentation::Integer => {
+                    "enums with an integer representation can only have unit variants"
+                }
+                _ if !variant.other => continue,
+                _ if self.representation == EnumRepresentation::Untagged => {
+                    "untagged enums can't have an other variant"
+                }
+                VariantFields::Unnamed(fields) if fields.is_empty() => continue,
+                _ => "only unit variants can be the other variant",
+            };
+            return Err(TypeGenError::UnsupportedVariant {
+                name: self.name.0.clone(),
+                variant: variant.name.0.clone(),
+                reason: reason.to_string(),
+            });
+        }
+        if let EnumRepresentation::Internal { .. } = self.representation {
+            for variant in &self.variants {
+                let reason = match &variant.fields {
+                    VariantFields::Unnamed(fields) if fields.len() > 1 => {
+                        "internally tagged enums can't contain tuple variants"
+                    }
+                    VariantFields::Unnamed(fields)
+                        if fields.len() == 1 && !fields[0].is_object() =>
+                    {
+                        "the payload of an internally tagged variant must be a struct or map"
+                    }
+                    _ => continue,
+                };
+                return Err(TypeGenError::UnsupportedVariant {
+                    name: self.name.0.clone(),
+                    variant: variant.name.0.clone(),
+                    reason: reason.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn collect_helpers(&self, output: &mut BTreeSet<ElmHelper>) {
+        for variant in &self.variants {
+            if let VariantFields::Named(fields) = &variant.fields {
+                for field in fields {
+                    field.collect_helpers(output);
+                }
+            }
+        }
+        if let EnumRepresentation::Internal { .. } = self.representation {
+            let has_newtype = self.variants.iter().any(|variant| {
+                matches!(&variant.fields, VariantFields::Unnamed(fields) if fields.len() == 1)
+            });
+            if has_newtype {
+                output.insert(ElmHelper::ObjectPairs);
+            }
+        }
+    }
+}
+
+impl ElmEnumVariant {
+    /// Matches the variant in a case expression and binds its fields:
+    /// `Started`, `Scaled x` or `Compare x0 x1`.
+    fn case_pattern(&self) -> String {
+        match &self.fields {
+            VariantFields::Unnamed(fields) if fields.is_empty() => self.name.0.clone(),
+            VariantFields::Unnamed(fields) if fields.len() > 1 => {
+                let mut pattern = self.name.0.clone();
+                for i in 0..fields.len() {
+                    pattern.push_str(&format!(" x{}", i));
+                }
+                pattern
+            }
+            VariantFields::Unnamed(_) | VariantFields::Named(_) => format!("{} x", self.name.0),
+        }
+    }
+
+    /// serde's default: `"Started"` or `{"Moved": payload}`
+    fn external_encoder(&self) -> String {
+        let fields = match &self.fields {
+            VariantFields::Unnamed(fields) => fields,
+            VariantFields::Named(fields) => {
+                // Json.Encode.object
+                //     [ ( "Moved"
+                //       , Json.Encode.object
+                //             [ ( "x", Json.Encode.int x.x )
+                //             ]
+                //       )
+                //     ]
+                let mut output = "            Json.Encode.object\n".to_string();
+                output.push_str(&format!(
+                    "                [ ( \"{}\"\n",
+                    self.serialized_name
+                ));
+                let payload = object_encoder(fields, "x", None);
+                output.push_str(&format!("                  , {}\n", payload[0]));
+                output.push_str(&indent_lines(&payload[1..], 20));
+                output.push_str("                  )\n");
+                output.push_str("                ]\n");
+                return output;
+            }
+        };
+        if fields.is_empty() {
+            format!(
+                "            Json.Encode.string \"{}\"\n",
+                self.serialized_name
+            )
+        } else if fields.len() == 1 {
+            let field = fields.first().unwrap();
+            let mut output = "            Json.Encode.object\n".to_string();
+            output.push_str(&format!(
+                "                [ ( \"{}\", {} x )\n",
+                self.serialized_name,
+                field.encoder_ref()
+            ));
+            output.push_str("                ]\n");
+            output
+        } else {
+            let mut output = "            Json.Encode.object\n".to_string();
+            output.push_str(&format!(
+                "                [ ( \"{}\"\n",
+                self.serialized_name
+            ));
+            output.push_str("                  , Json.Encode.list (\\v -> v)\n");
+            let mut is_first = true;
+            for (i, field) in fields.iter().enumerate() {
+                if is_first {
+                    output.push_str("                        [ ");
+                    is_first = false;
+                } else {
+                    output.push_str("                        , ");
+                }
+                output.push_str(&format!("{} x{}\n", field.encoder_ref(), i));
+            }
+            output.push_str("                        ]\n");
+            output.push_str("                  )\n");
+            output.push_str("                ]\n");
+            output
+        }
+    }
+
+    /// `#[serde(tag = "type")]`: `{"type": "Moved", "x": 1, "y": 2}`
+    fn internal_encoder(&self, tag: &str) -> String {
+        let tag_field = format!(
+            "( \"{}\", Json.Encode.string \"{}\" )",
+            tag, self.serialized_name
+        );
+        match &self.fields {
+            VariantFields::Unnamed(fields) if fields.len() == 1 => {
+                // Tuple variants are rejected by `validate_representation`.
+                let mut output = "            Json.Encode.object\n".to_string();
+                output.push_str(&format!(
+                    "                ({} :: objectPairs ({} x))\n",
+                    tag_field,
+                    fields[0].encoder_ref()
                 ));
-                output.push_str("                _ ->\n");
+                output
+            }
+            VariantFields::Unnamed(_) => {
+                indent_lines(&object_encoder(&[], "x", Some(tag_field)), 12)
+            }
+            VariantFields::Named(fields) => {
+                indent_lines(&object_encoder(fields, "x", Some(tag_field)), 12)
+            }
+        }
+    }
+
+    /// All tags that are accepted when decoding, the serialized name first.
+    fn tags(&self) -> Vec<String> {
+        let mut tags = vec![self.serialized_name.clone()];
+        tags.extend(self.aliases.iter().cloned());
+        tags
+    }
+
+    fn external_decoder(&self) -> String {
+        let tags = self.tags();
+        if self.is_unit() || tags.len() == 1 {
+            return self.external_tag_decoder(&tags);
+        }
+        // Json.Decode.oneOf
+        //     [ Json.Decode.field "Moved"
+        //         ...
+        //     , Json.Decode.field "Move"
+        //         ...
+        //     ]
+        let mut output = "    Json.Decode.oneOf\n".to_string();
+        for (i, tag) in tags.iter().enumerate() {
+            let prefix = if i == 0 { "[" } else { "," };
+            let decoder = self.external_tag_decoder(std::slice::from_ref(tag));
+            for (j, line) in decoder.lines().enumerate() {
+                if j == 0 {
+                    output.push_str(&format!("        {} {}\n", prefix, line.trim_start()));
+                } else {
+                    output.push_str(&format!("    {}\n", line));
+                }
+            }
+        }
+        output.push_str("        ]\n");
+        output
+    }
+
+    /// Decodes the variant with one of these tags. Only unit variants accept
+    /// more than one, the others are combined in `external_decoder`.
+    fn external_tag_decoder(&self, tags: &[String]) -> String {
+        let tag = &tags[0];
+        let fields = match &self.fields {
+            VariantFields::Unnamed(fields) => fields,
+            VariantFields::Named(fields) => {
+                // Json.Decode.field "Moved"
+                //     (Json.Decode.succeed (\x y -> Moved { x = x, y = y })
+                //         |> Json.Decode.Pipeline.required "x" Json.Decode.int
+                //         |> Json.Decode.Pipeline.required "y" Json.Decode.int
+                //     )
+                let mut output = format!("    Json.Decode.field \"{}\"\n", tag);
                 output.push_str(&format!(
-                    "                    Json.Decode.fail \"Expected variant {}\"\n",
-                    variant.name.0
+                    "        (Json.Decode.succeed {}\n",
+                    record_constructor(&self.name, fields)
                 ));
+                for field in fields {
+                    output.push_str(&format!("            |> {}\n", field.pipeline_decoder()));
+                }
                 output.push_str("        )\n");
-                output.push_str("        Json.Decode.string\n")
-            } else if variant.fields.len() == 1 {
-                output.push_str(&format!("    Json.Decode.succeed {}\n", variant.name.0));
-                let field = variant.fields.first().unwrap();
+                return output;
+            }
+        };
+        let mut output = String::new();
+        if fields.is_empty() {
+            // TODO: All those variants should be joined together for performance.
+            output.push_str("    Json.Decode.andThen\n");
+            output.push_str("        (\\str ->\n");
+            output.push_str("            case str of\n");
+            for tag in tags {
+                output.push_str(&format!("                \"{}\" ->\n", tag));
+                output.push_str(&format!(
+                    "                    Json.Decode.succeed {}\n\n",
+                    self.name.0
+                ));
+            }
+            output.push_str("                _ ->\n");
+            output.push_str(&format!(
+                "                    Json.Decode.fail \"Expected variant {}\"\n",
+                tag
+            ));
+            output.push_str("        )\n");
+            output.push_str("        Json.Decode.string\n")
+        } else if fields.len() == 1 {
+            output.push_str(&format!("    Json.Decode.succeed {}\n", self.name.0));
+            let field = fields.first().unwrap();
+            output.push_str(&format!(
+                "        |> Json.Decode.Pipeline.required \"{}\" {}\n",
+                tag,
+                field.decoder_ref()
+            ));
+        } else {
+            output.push_str(&format!("    Json.Decode.succeed {}\n", self.name.0));
+            for (i, ty) in fields.iter().enumerate() {
+                // |> Json.Decode.Pipeline.custom
+                //     (Json.Decode.field "Compare" (Json.Decode.index 0 Json.Decode.int))
+                output.push_str("        |> Json.Decode.Pipeline.custom \n");
+                output.push_str(&format!(
+                    "            (Json.Decode.field \"{}\" (Json.Decode.index {} {}))\n",
+                    tag,
+                    i,
+                    ty.decoder_ref()
+                ));
+            }
+        }
+        output
+    }
+
+    /// The tag was already checked, so this only reads the other fields.
+    fn internal_decoder(&self) -> String {
+        if self.is_unit() {
+            format!("    Json.Decode.succeed {}\n", self.name.0)
+        } else {
+            indent_lines(&self.payload_decoder(), 4)
+        }
+    }
+
+    /// `#[serde(tag = "t", content = "c")]`: `{"t": "Moved", "c": payload}`
+    fn adjacent_encoder(&self, tag: &str, content: &str) -> String {
+        let mut output = "            Json.Encode.object\n".to_string();
+        output.push_str(&format!(
+            "                [ ( \"{}\", Json.Encode.string \"{}\" )\n",
+            tag, self.serialized_name
+        ));
+        // Unit variants only have the tag.
+        if !self.is_unit() {
+            let payload = self.payload_encoder();
+            if let [line] = payload.as_slice() {
                 output.push_str(&format!(
-                    "        |> Json.Decode.Pipeline.required \"{}\" {}\n",
-                    variant.name.0,
-                    field.decoder_ref()
+                    "                , ( \"{}\", {} )\n",
+                    content, line
                 ));
             } else {
-                output.push_str(&format!("    Json.Decode.succeed {}\n", variant.name.0));
-                for (i, ty) in variant.fields.iter().enumerate() {
-                    // |> Json.Decode.Pipeline.custom
-                    //     (Json.Decode.field "Compare" (Json.Decode.index 0 Json.Decode.int))
-                    output.push_str("        |> Json.Decode.Pipeline.custom \n");
-                    output.push_str(&format!(
-                        "            (Json.Decode.field \"{}\" (Json.Decode.index {} {}))\n",
-                        variant.name.0,
+                output.push_str(&format!("                , ( \"{}\"\n", content));
+                output.push_str(&format!("                  , {}\n", payload[0]));
+                output.push_str(&indent_lines(&payload[1..], 20));
+                output.push_str("                  )\n");
+            }
+        }
+        output.push_str("                ]\n");
+        output
+    }
+
+    /// The tag was already checked, so this only reads the content field.
+    fn adjacent_decoder(&self, content: &str) -> String {
+        if self.is_unit() {
+            return format!("    Json.Decode.succeed {}\n", self.name.0);
+        }
+        let payload = self.payload_decoder();
+        if let [line] = payload.as_slice() {
+            format!("    Json.Decode.field \"{}\" ({})\n", content, line)
+        } else {
+            let mut output = format!("    Json.Decode.field \"{}\"\n", content);
+            output.push_str(&format!("        ({}\n", payload[0]));
+            output.push_str(&indent_lines(&payload[1..], 8));
+            output.push_str("        )\n");
+            output
+        }
+    }
+
+    fn is_unit(&self) -> bool {
+        matches!(&self.fields, VariantFields::Unnamed(fields) if fields.is_empty())
+    }
+
+    /// Encodes the fields of the variant without any tag. Lines after the
+    /// first one are indented relative to it.
+    fn payload_encoder(&self) -> Vec<String> {
+        match &self.fields {
+            VariantFields::Unnamed(fields) if fields.is_empty() => {
+                vec!["Json.Encode.null".to_string()]
+            }
+            VariantFields::Unnamed(fields) if fields.len() == 1 => {
+                vec![format!("{} x", fields[0].encoder_ref())]
+            }
+            VariantFields::Unnamed(fields) => {
+                let mut lines = vec!["Json.Encode.list identity".to_string()];
+                for (i, field) in fields.iter().enumerate() {
+                    let prefix = if i == 0 { "[" } else { "," };
+                    lines.push(format!("    {} {} x{}", prefix, field.encoder_ref(), i));
+                }
+                lines.push("    ]".to_string());
+                lines
+            }
+            VariantFields::Named(fields) => object_encoder(fields, "x", None),
+        }
+    }
+
+    /// Decodes the fields of the variant without any tag. Lines after the
+    /// first one are indented relative to it.
+    fn payload_decoder(&self) -> Vec<String> {
+        match &self.fields {
+            VariantFields::Unnamed(fields) if fields.is_empty() => {
+                vec![format!("Json.Decode.null {}", self.name.0)]
+            }
+            VariantFields::Unnamed(fields) if fields.len() == 1 => {
+                vec![format!(
+                    "Json.Decode.map {} {}",
+                    self.name.0,
+                    fields[0].decoder_ref()
+                )]
+            }
+            VariantFields::Unnamed(fields) => {
+                let mut lines = vec![format!("Json.Decode.succeed {}", self.name.0)];
+                for (i, field) in fields.iter().enumerate() {
+                    lines.push(format!(
+                        "    |> Json.Decode.Pipeline.custom (Json.Decode.index {} {})",
                         i,
-                        ty.decoder_ref()
+                        field.decoder_ref()
                     ));
                 }
+                lines
+            }
+            VariantFields::Named(fields) => {
+                let mut lines = vec![format!(
+                    "Json.Decode.succeed {}",
+                    record_constructor(&self.name, fields)
+                )];
+                for field in fields {
+                    lines.push(format!("    |> {}", field.pipeline_decoder()));
+                }
+                lines
+            }
+        }
+    }
+}
+
+fn indent_lines(lines: &[String], indent: usize) -> String {
+    let indent = " ".repeat(indent);
+    lines
+        .iter()
+        .map(|line| format!("{}{}\n", indent, line))
+        .collect()
+}
+
+/// Encodes the fields of `record` as an object, optionally starting with a
+/// tag entry. Lines after the first one are indented relative to it.
+fn object_encoder(fields: &[ElmField], record: &str, tag: Option<String>) -> Vec<String> {
+    // Json.Encode.object
+    //     [ ( "x", Json.Encode.int x.x )
+    //     ]
+    //
+    // or, when optional fields are left out:
+    //
+    // Json.Encode.object
+    //     (List.filterMap identity
+    //         [ Just ( "x", Json.Encode.int x.x )
+    //         , Maybe.map (Tuple.pair "y" << Json.Encode.int) x.y
+    //         ]
+    //     )
+    //
+    // Flattened fields are appended with `++ objectPairs (encodeMeta x.meta)`
+    // to the filtered list.
+    let flattened: Vec<&ElmField> = fields.iter().filter(|f| f.flatten.is_some()).collect();
+    let omit = !flattened.is_empty() || fields.iter().any(ElmField::is_omitted_when_nothing);
+    let always = |entry: String| {
+        if omit {
+            format!("Just {}", entry)
+        } else {
+            entry
+        }
+    };
+    let mut entries: Vec<String> = tag.into_iter().map(always).collect();
+    for field in fields {
+        if field.flatten.is_some() {
+            continue;
+        }
+        match &field.ty {
+            ElmType::Maybe(inner) if field.is_omitted_when_nothing() => {
+                entries.push(format!(
+                    "Maybe.map (Tuple.pair \"{}\" << {}) {}.{}",
+                    field.serialized_name,
+                    inner.encoder_ref(),
+                    record,
+                    field.elm_name()
+                ));
             }
+            _ => entries.push(always(format!(
+                "( \"{}\", {} {}.{} )",
+                field.serialized_name,
+                field.ty.encoder_ref(),
+                record,
+                field.elm_name()
+            ))),
         }
+    }
 
-        output
+    let mut lines = vec!["Json.Encode.object".to_string()];
+    let indent = if omit {
+        lines.push("    (List.filterMap identity".to_string());
+        "        "
+    } else {
+        "    "
+    };
+    if entries.is_empty() {
+        lines.push(format!("{}[]", indent));
+    } else {
+        for (i, entry) in entries.iter().enumerate() {
+            let prefix = if i == 0 { "[" } else { "," };
+            lines.push(format!("{}{} {}", indent, prefix, entry));
+        }
+        lines.push(format!("{}]", indent));
+    }
+    for field in flattened {
+        lines.push(format!(
+            "{}++ objectPairs ({} {}.{})",
+            indent,
+            field.ty.encoder_ref(),
+            record,
+            field.elm_name()
+        ));
     }
+    if omit {
+        lines.push("    )".to_string());
+    }
+    lines
 }
 
 #[derive(Debug)]
@@ -450,34 +2026,101 @@ pub struct RustFile {
     pub all_enums: HashMap<Identifier, ElmEnum>,
     /// All enums that are exported into the target elm file
     pub export_enums: Vec<ElmEnum>,
+    /// Problems in the exported types that don't stop the generation, like
+    /// untagged variants that can never be decoded.
+    pub warnings: Vec<String>,
+    /// The rust path of every struct and enum, like `["game", "Card"]`.
+    pub definition_paths: HashMap<Identifier, Vec<String>>,
 }
 
 impl RustFile {
     pub fn parse(ast: &syn::File) -> Result<RustFile> {
-        let main_export_types = discover_export_types(ast)?;
-        let mut main_export_enums = Vec::new();
+        Self::parse_with_options(ast, &TypeGenOptions::default())
+    }
 
-        let all_structs = find_all_structs(ast)?;
+    /// Reads the crate root, like `src/lib.rs`, and all modules below it.
+    pub fn parse_crate(root: &Path, options: &TypeGenOptions) -> Result<RustFile> {
+        Self::parse_modules(&modules::load_crate(root)?, options)
+    }
+
+    /// Modules written inline in the file are read as well.
+    pub fn parse_with_options(ast: &syn::File, options: &TypeGenOptions) -> Result<RustFile> {
+        let mut modules = vec![];
+        modules::inline_modules(ast.items.clone(), vec![], &mut modules);
+        Self::parse_modules(&modules, options)
+    }
 
+    fn parse_modules(modules: &[RustModule], options: &TypeGenOptions) -> Result<RustFile> {
+        let names = NameTable::new(modules);
+        let mut main_export_types = discover_export_types(modules, &names)?;
+        // Types that are never exported don't need to be convertible.
+        let mut errors = HashMap::new();
+        let mut all_structs = find_all_structs(modules, &names, options, &mut errors);
+        let mut all_enums = find_all_enums(modules, &names, options, &mut errors);
+        resolve_flattened_maps(all_structs.values_mut(), all_enums.values_mut());
+
+        // Walk all exported types and pull in everything they reference.
+        // Each queue entry remembers who referenced it for error reporting.
         let mut export_structs: Vec<ElmStruct> = vec![];
-        for identifier in &main_export_types {
-            if let Some(struct_) = all_structs.get(identifier) {
+        let mut export_enums: Vec<ElmEnum> = vec![];
+        let mut seen = HashSet::new();
+        let mut queue: VecDeque<(Identifier, Option<Identifier>)> = main_export_types
+            .iter()
+            .map(|identifier| (identifier.clone(), None))
+            .collect();
+        while let Some((identifier, referenced_by)) = queue.pop_front() {
+            if !seen.insert(identifier.clone()) {
+                continue;
+            }
+            let referenced_types = if let Some(struct_) = all_structs.get(&identifier) {
                 export_structs.push(struct_.clone());
+                struct_.referenced_types()
+            } else if let Some(enum_) = all_enums.get(&identifier) {
+                export_enums.push(enum_.clone());
+                enum_.referenced_types()
+            } else if let Some(error) = errors.remove(&identifier) {
+                return Err(error);
             } else {
-                main_export_enums.push(identifier.clone());
+                return Err(TypeGenError::UndefinedType {
+                    name: identifier.0,
+                    referenced_by: referenced_by
+                        .map_or_else(|| "impl ElmExport".to_string(), |r| r.0),
+                });
+            };
+            for referenced in referenced_types {
+                queue.push_back((referenced, Some(identifier.clone())));
             }
         }
 
-        let all_enums = find_all_enums(ast)?;
-
-        let mut export_enums: Vec<ElmEnum> = vec![];
-        for identifier in &main_export_enums {
-            if let Some(enum_) = all_enums.get(identifier) {
-                export_enums.push(enum_.clone());
-            } else {
-                panic!("Enum/Struct {:?} not found", identifier);
+        // Only exported types decide which names need a module prefix.
+        let exported: Vec<&Identifier> = (export_structs.iter().map(|s| &s.name))
+            .chain(export_enums.iter().map(|e| &e.name))
+            .collect();
+        let elm_names = names.elm_names(&exported)?;
+        rename_definitions(&elm_names, &mut export_structs, &mut export_enums);
+        for name in &mut main_export_types {
+            if let Some(elm_name) = elm_names.get(name) {
+                *name = elm_name.clone();
             }
         }
+        rename_definitions(&elm_names, all_structs.values_mut(), all_enums.values_mut());
+        let all_structs: HashMap<Identifier, ElmStruct> = (all_structs.into_values())
+            .map(|s| (s.name.clone(), s))
+            .collect();
+        let all_enums = (all_enums.into_values())
+            .map(|e| (e.name.clone(), e))
+            .collect();
+        let definition_paths = elm_names
+            .iter()
+            .filter_map(|(name, elm_name)| Some((elm_name.clone(), names.definition_path(name)?)))
+            .collect();
+
+        resolve_map_keys(&mut export_structs, &mut export_enums)?;
+
+        let warnings = export_enums
+            .iter()
+            .flat_map(|enum_| shape::untagged_warnings(enum_, &all_structs))
+            .collect();
 
         Ok(RustFile {
             main_export_types,
@@ -485,115 +2128,639 @@ impl RustFile {
             export_structs,
             all_enums,
             export_enums,
+            warnings,
+            definition_paths,
         })
     }
 }
 
-fn find_all_enums(ast: &syn::File) -> Result<HashMap<Identifier, ElmEnum>> {
+/// Enums that can't be converted end up in `errors`, which only matter if
+/// they are exported.
+fn find_all_enums(
+    modules: &[RustModule],
+    names: &NameTable,
+    options: &TypeGenOptions,
+    errors: &mut HashMap<Identifier, TypeGenError>,
+) -> HashMap<Identifier, ElmEnum> {
     let mut result = HashMap::new();
 
-    for item in &ast.items {
-        if let syn::Item::Enum(item_enum) = item {
-            let identifier = Identifier(item_enum.ident.to_string());
-            let mut variants = vec![];
-            for variant in &item_enum.variants {
-                let var_ident = Identifier(variant.ident.to_string());
-                let mut fields = vec![];
-                for field in variant.fields.iter() {
-                    let ty = elm_type_from_type(&field.ty)?;
-                    fields.push(ty);
+    for module in modules {
+        let names = names.scope(&module.path);
+        for item in &module.items {
+            if let syn::Item::Enum(item_enum) = item {
+                match extract_elm_enum(item_enum, names, options) {
+                    Ok(Some(elm_enum)) => {
+                        result.insert(elm_enum.name.clone(), elm_enum);
+                    }
+                    Ok(None) => {}
+                    Err(error) => {
+                        errors.insert(names.elm_name(&item_enum.ident), error);
+                    }
                 }
-                variants.push(ElmEnumVariant {
-                    name: var_ident,
-                    fields,
-                });
             }
-            result.insert(
-                identifier.clone(),
-                ElmEnum {
-                    name: identifier,
-                    variants,
-                },
-            );
         }
     }
 
-    Ok(result)
+    result
 }
 
-fn discover_export_types(ast: &syn::File) -> Result<Vec<Identifier>> {
-    let mut main_export_types = Vec::new();
-    for item in &ast.items {
-        if let syn::Item::Impl(item_impl) = item {
-            if let Some((_, item_impl_trait, _)) = &item_impl.trait_ {
-                let trait_ident = last_path(item_impl_trait)?;
-
-                if trait_ident.0 == "ElmExport" {
-                    if let syn::Type::Path(type_path) = item_impl.self_ty.as_ref() {
-                        let type_for_export = simple_path(&type_path.path)?;
-                        main_export_types.push(type_for_export);
-                    }
+/// `None` for enums that are converted into another type, those are structs.
+fn extract_elm_enum(
+    item_enum: &syn::ItemEnum,
+    names: ModuleScope,
+    options: &TypeGenOptions,
+) -> Result<Option<ElmEnum>> {
+    let enum_attributes = SerdeAttributes::parse(&item_enum.attrs)?;
+    let integer = attributes::is_integer_enum(&item_enum.attrs)?;
+    if enum_attributes.conversion_type()?.is_some() {
+        // Written as another type, see `extract_converted_enum`.
+        return Ok(None);
+    }
+    let identifier = names.elm_name(&item_enum.ident);
+    let generics = type_parameters(&item_enum.generics);
+    let scope = Scope {
+        options,
+        generics: &generics,
+        names,
+    };
+    let mut variants = vec![];
+    let mut next_discriminant = 0;
+    for variant in &item_enum.variants {
+        let discriminant = if integer {
+            let value = match &variant.discriminant {
+                Some((_, expr)) => discriminant_value(expr)?,
+                None => next_discriminant,
+            };
+            next_discriminant = value + 1;
+            Some(value)
+        } else {
+            None
+        };
+        let var_ident = Identifier(variant.ident.to_string());
+        let attributes = SerdeAttributes::parse(&variant.attrs)?;
+        let serialized_name = match (&attributes.rename, enum_attributes.rename_all) {
+            (Some(rename), _) => rename.clone(),
+            (None, Some(rule)) => rule.apply_to_variant(&var_ident.0),
+            (None, None) => var_ident.0.clone(),
+        };
+        let aliases = attributes.aliases.clone();
+        let other = attributes.other;
+        let fields = match &variant.fields {
+            syn::Fields::Named(named) => {
+                let container = SerdeAttributes {
+                    rename_all: attributes.rename_all.or(enum_attributes.rename_all_fields),
+                    ..attributes
+                };
+                VariantFields::Named(extract_named_fields(named, &container, &scope)?)
+            }
+            syn::Fields::Unnamed(_) | syn::Fields::Unit => {
+                let mut fields = vec![];
+                for field in variant.fields.iter() {
+                    fields.push(elm_type_from_type(&field.ty, &scope)?);
                 }
+                VariantFields::Unnamed(fields)
             }
+        };
+        variants.push(ElmEnumVariant {
+            name: var_ident,
+            serialized_name,
+            aliases,
+            other,
+            discriminant,
+            fields,
+        });
+    }
+    let representation = match (enum_attributes.tag, enum_attributes.content) {
+        _ if integer => EnumRepresentation::Integer,
+        _ if enum_attributes.untagged => EnumRepresentation::Untagged,
+        (Some(tag), Some(content)) => EnumRepresentation::Adjacent { tag, content },
+        (Some(tag), None) => EnumRepresentation::Internal { tag },
+        (None, _) => EnumRepresentation::External,
+    };
+    let elm_enum = ElmEnum {
+        name: identifier,
+        generics,
+        variants,
+        representation,
+    };
+    elm_enum.validate_representation()?;
+    Ok(Some(elm_enum))
+}
+
+/// The value of `Red = 1` or `Cold = -1`.
+fn discriminant_value(expr: &syn::Expr) -> Result<i64> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(int),
+            ..
+        }) => Ok(int.base10_parse()?),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => Ok(-discriminant_value(expr)?),
+        _ => {
+            let message = "Only integer literals are supported as discriminants";
+            Err(syn::Error::new_spanned(expr, message).into())
+        }
+    }
+}
+
+fn discover_export_types(modules: &[RustModule], names: &NameTable) -> Result<Vec<Identifier>> {
+    let mut main_export_types = Vec::new();
+    for module in modules {
+        let names = names.scope(&module.path);
+        for item in &module.items {
+            discover_export_type(item, names, &mut main_export_types)?;
         }
     }
     Ok(main_export_types)
 }
 
-fn find_all_structs(ast: &syn::File) -> Result<HashMap<Identifier, ElmStruct>> {
+fn discover_export_type(
+    item: &syn::Item,
+    names: ModuleScope,
+    main_export_types: &mut Vec<Identifier>,
+) -> Result<()> {
+    let derived = match item {
+        syn::Item::Struct(item_struct) => Some((&item_struct.ident, &item_struct.attrs)),
+        syn::Item::Enum(item_enum) => Some((&item_enum.ident, &item_enum.attrs)),
+        _ => None,
+    };
+    if let Some((ident, attrs)) = derived {
+        if attributes::derived_traits(attrs)?
+            .iter()
+            .any(|t| t == "ElmExport")
+        {
+            main_export_types.push(names.elm_name(ident));
+        }
+    }
+    if let syn::Item::Impl(item_impl) = item {
+        if let Some((_, item_impl_trait, _)) = &item_impl.trait_ {
+            let trait_ident = last_path(item_impl_trait)?;
+
+            if trait_ident.0 == "ElmExport" {
+                if let syn::Type::Path(type_path) = item_impl.self_ty.as_ref() {
+                    let type_for_export = match names.resolve(&type_path.path) {
+                        ResolvedPath::Definition(identifier) => identifier,
+                        ResolvedPath::Name(identifier) => identifier,
+                    };
+                    main_export_types.push(type_for_export);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Like `find_all_enums`, structs that can't be converted end up in `errors`.
+fn find_all_structs(
+    modules: &[RustModule],
+    names: &NameTable,
+    options: &TypeGenOptions,
+    errors: &mut HashMap<Identifier, TypeGenError>,
+) -> HashMap<Identifier, ElmStruct> {
     let mut result = HashMap::new();
 
-    for item in &ast.items {
-        if let syn::Item::Struct(item_struct) = item {
-            let identifier = Identifier(item_struct.ident.to_string());
-            match &item_struct.fields {
-                syn::Fields::Named(fields) => {
-                    result.insert(identifier.clone(), extract_elm_struct(identifier, fields)?);
+    for module in modules {
+        let names = names.scope(&module.path);
+        for item in &module.items {
+            let (ident, elm_struct) = match item {
+                syn::Item::Struct(item_struct) => (
+                    &item_struct.ident,
+                    extract_elm_struct(item_struct, names, options).map(Some),
+                ),
+                syn::Item::Enum(item_enum) => (
+                    &item_enum.ident,
+                    extract_converted_enum(item_enum, names, options),
+                ),
+                _ => continue,
+            };
+            match elm_struct {
+                Ok(Some(elm_struct)) => {
+                    result.insert(elm_struct.name.clone(), elm_struct);
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    errors.insert(names.elm_name(ident), error);
                 }
-                syn::Fields::Unnamed(_) => return Err(TypeGenError::UnnamedStructsNotSupported),
-                syn::Fields::Unit => return Err(TypeGenError::UnnamedStructsNotSupported),
             }
         }
     }
 
-    Ok(result)
+    result
+}
+
+/// An enum converted into another type is a newtype in elm.
+fn extract_converted_enum(
+    item_enum: &syn::ItemEnum,
+    names: ModuleScope,
+    options: &TypeGenOptions,
+) -> Result<Option<ElmStruct>> {
+    let attributes = SerdeAttributes::parse(&item_enum.attrs)?;
+    let conversion_type = match attributes.conversion_type()? {
+        Some(conversion_type) => conversion_type,
+        None => return Ok(None),
+    };
+    let identifier = names.elm_name(&item_enum.ident);
+    let generics = type_parameters(&item_enum.generics);
+    let scope = Scope {
+        options,
+        generics: &generics,
+        names,
+    };
+    let fields = StructFields::Newtype {
+        inner: elm_type_from_type(conversion_type, &scope)?,
+        opaque: options.opaque_newtypes.contains(&identifier.0)
+            || ElmAttributes::parse(&item_enum.attrs)?.opaque,
+    };
+    Ok(Some(ElmStruct {
+        name: identifier,
+        generics,
+        fields,
+    }))
 }
 
-fn extract_elm_struct(identifier: Identifier, fields: &syn::FieldsNamed) -> Result<ElmStruct> {
-    let mut result = ElmStruct {
+fn extract_elm_struct(
+    item_struct: &syn::ItemStruct,
+    names: ModuleScope,
+    options: &TypeGenOptions,
+) -> Result<ElmStruct> {
+    let identifier = names.elm_name(&item_struct.ident);
+    let generics = type_parameters(&item_struct.generics);
+    let attributes = SerdeAttributes::parse(&item_struct.attrs)?;
+    let scope = Scope {
+        options,
+        generics: &generics,
+        names,
+    };
+    let opaque = options.opaque_newtypes.contains(&identifier.0)
+        || ElmAttributes::parse(&item_struct.attrs)?.opaque;
+    // Both are written as another type, so they become newtypes of it.
+    if let Some(conversion_type) = attributes.conversion_type()? {
+        return Ok(ElmStruct {
+            fields: StructFields::Newtype {
+                inner: elm_type_from_type(conversion_type, &scope)?,
+                opaque,
+            },
+            name: identifier,
+            generics,
+        });
+    }
+    if attributes.transparent {
+        return Ok(ElmStruct {
+            fields: StructFields::Newtype {
+                inner: elm_type_from_type(transparent_field(item_struct)?, &scope)?,
+                opaque,
+            },
+            name: identifier,
+            generics,
+        });
+    }
+    let fields = match &item_struct.fields {
+        syn::Fields::Named(fields) => {
+            StructFields::Named(extract_named_fields(fields, &attributes, &scope)?)
+        }
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => StructFields::Newtype {
+            inner: elm_type_from_type(&fields.unnamed[0].ty, &scope)?,
+            opaque,
+        },
+        syn::Fields::Unnamed(fields) => {
+            let mut result_fields = vec![];
+            for field in &fields.unnamed {
+                result_fields.push(elm_type_from_type(&field.ty, &scope)?);
+            }
+            StructFields::Tuple(result_fields)
+        }
+        syn::Fields::Unit => StructFields::Unit,
+    };
+    Ok(ElmStruct {
         name: identifier,
-        fields: vec![],
+        generics,
+        fields,
+    })
+}
+
+/// The type of the only field of a `#[serde(transparent)]` struct that is not
+/// skipped.
+fn transparent_field(item_struct: &syn::ItemStruct) -> Result<&syn::Type> {
+    let mut fields = vec![];
+    for field in &item_struct.fields {
+        if !SerdeAttributes::parse(&field.attrs)?.skip {
+            fields.push(&field.ty);
+        }
+    }
+    match fields[..] {
+        [ty] => Ok(ty),
+        _ => {
+            let message = "A transparent struct needs exactly one field that is not skipped";
+            Err(syn::Error::new_spanned(&item_struct.ident, message).into())
+        }
+    }
+}
+
+/// Gives the definitions in `elm_names` their new name, everywhere they
+/// are defined or referenced.
+fn rename_definitions<'a>(
+    elm_names: &HashMap<Identifier, Identifier>,
+    structs: impl IntoIterator<Item = &'a mut ElmStruct>,
+    enums: impl IntoIterator<Item = &'a mut ElmEnum>,
+) {
+    let rename = |name: &mut Identifier| {
+        if let Some(elm_name) = elm_names.get(name) {
+            *name = elm_name.clone();
+        }
+    };
+    let rename_type = |ty: &mut ElmType| {
+        ty.walk_mut(&mut |ty| {
+            if let ElmType::NamedType(name, _) = ty {
+                rename(name);
+            }
+        });
     };
+    for struct_ in structs {
+        rename(&mut struct_.name);
+        struct_.field_types_mut().into_iter().for_each(rename_type);
+    }
+    for enum_ in enums {
+        rename(&mut enum_.name);
+        enum_.field_types_mut().for_each(rename_type);
+    }
+}
+
+/// `container` are the attributes of the struct or struct-like variant.
+fn extract_named_fields(
+    fields: &syn::FieldsNamed,
+    container: &SerdeAttributes,
+    scope: &Scope,
+) -> Result<Vec<ElmField>> {
+    let mut result = vec![];
     for field in &fields.named {
-        let ident = Identifier(field.ident.as_ref().unwrap().to_string());
-        let ty = elm_type_from_type(&field.ty)?;
-        result.fields.push((ident, ty));
+        let name = Identifier(field.ident.as_ref().unwrap().to_string());
+        let attributes = SerdeAttributes::parse(&field.attrs)?;
+        if attributes.skip || (attributes.skip_serializing && attributes.skip_deserializing) {
+            continue;
+        }
+        let serialized_name = match (attributes.rename, container.rename_all) {
+            (Some(rename), _) => rename,
+            (None, Some(rule)) => rule.apply_to_field(&name.0),
+            (None, None) => name.0.clone(),
+        };
+        let mut ty = elm_type_from_type(&field.ty, scope)?;
+        let flatten = if attributes.flatten {
+            match &ty {
+                ElmType::NamedType(_, _) | ElmType::TypeVariable(_) => Some(Flatten::Struct),
+                ElmType::Dict(key, _) if matches!(key.as_ref(), ElmType::String) => {
+                    // Filled in by `resolve_flattened_maps` once all structs are known.
+                    Some(Flatten::Map { known_keys: vec![] })
+                }
+                _ => {
+                    let message = "Only structs and maps with string keys can be flattened";
+                    return Err(syn::Error::new(field.span(), message).into());
+                }
+            }
+        } else {
+            None
+        };
+        let may_be_missing = container.default
+            || attributes.default
+            || attributes.skip_serializing_if
+            || attributes.skip_serializing;
+        let default = if may_be_missing {
+            // We can't know the rust default of other types, so elm gets a
+            // Maybe for them.
+            if ty.default_value().is_none() {
+                ty = ElmType::Maybe(Box::new(ty));
+            }
+            ty.default_value()
+        } else {
+            None
+        };
+        result.push(ElmField {
+            name,
+            serialized_name,
+            ty,
+            default,
+            flatten,
+            aliases: attributes.aliases,
+        });
     }
     Ok(result)
 }
 
-fn elm_type_from_type(ty: &syn::Type) -> Result<ElmType> {
+/// A flattened map only gets the keys that no other field uses, including
+/// the fields of flattened structs.
+fn resolve_flattened_maps<'a>(
+    all_structs: impl IntoIterator<Item = &'a mut ElmStruct>,
+    all_enums: impl IntoIterator<Item = &'a mut ElmEnum>,
+) {
+    let mut all_structs: Vec<&mut ElmStruct> = all_structs.into_iter().collect();
+    let structs: HashMap<Identifier, ElmStruct> = all_structs
+        .iter()
+        .map(|struct_| (struct_.name.clone(), (*struct_).clone()))
+        .collect();
+    for struct_ in all_structs.iter_mut() {
+        if let StructFields::Named(fields) = &mut struct_.fields {
+            resolve_known_keys(fields, vec![], &structs);
+        }
+    }
+    for enum_ in all_enums {
+        // The tag of an internally tagged enum shares the object with the fields.
+        let tag_keys = match &enum_.representation {
+            EnumRepresentation::Internal { tag } => vec![tag.clone()],
+            _ => vec![],
+        };
+        for variant in &mut enum_.variants {
+            if let VariantFields::Named(fields) = &mut variant.fields {
+                resolve_known_keys(fields, tag_keys.clone(), &structs);
+            }
+        }
+    }
+}
+
+/// How a struct or enum works as a map key.
+enum MapKey {
+    /// Written like the type it wraps, which becomes the key instead.
+    Newtype(ElmType),
+    Valid,
+    Invalid,
+}
+
+/// Replaces newtype map keys by the type they wrap and rejects keys that
+/// serde_json can't write as an object key.
+fn resolve_map_keys(structs: &mut [ElmStruct], enums: &mut [ElmEnum]) -> Result<()> {
+    let mut map_keys = HashMap::new();
+    for struct_ in structs.iter() {
+        let map_key = match &struct_.fields {
+            StructFields::Newtype {
+                inner,
+                opaque: false,
+            } if struct_.generics.is_empty() => MapKey::Newtype(inner.clone()),
+            StructFields::Newtype { inner, .. }
+                if inner.is_comparable_key() || *inner == ElmType::Bool =>
+            {
+                MapKey::Valid
+            }
+            _ => MapKey::Invalid,
+        };
+        map_keys.insert(struct_.name.clone(), map_key);
+    }
+    for enum_ in enums.iter() {
+        let unit_only = enum_
+            .variants
+            .iter()
+            .all(|variant| matches!(&variant.fields, VariantFields::Unnamed(f) if f.is_empty()));
+        // Unit variants of other representations are written as null or objects.
+        let map_key = match enum_.representation {
+            EnumRepresentation::External | EnumRepresentation::Integer if unit_only => {
+                MapKey::Valid
+            }
+            _ => MapKey::Invalid,
+        };
+        map_keys.insert(enum_.name.clone(), map_key);
+    }
+    for struct_ in structs.iter_mut() {
+        let name = struct_.name.clone();
+        for ty in struct_.field_types_mut() {
+            resolve_map_keys_in(ty, &name, &map_keys)?;
+        }
+    }
+    for enum_ in enums.iter_mut() {
+        let name = enum_.name.clone();
+        for ty in enum_.field_types_mut() {
+            resolve_map_keys_in(ty, &name, &map_keys)?;
+        }
+    }
+    Ok(())
+}
+
+fn resolve_map_keys_in(
+    ty: &mut ElmType,
+    referenced_by: &Identifier,
+    map_keys: &HashMap<Identifier, MapKey>,
+) -> Result<()> {
+    let mut result = Ok(());
+    ty.walk_mut(&mut |ty| {
+        let key = match ty {
+            ElmType::Dict(key, _) => key,
+            _ => return,
+        };
+        let original = key.type_ref();
+        // Newtypes can wrap other newtypes, but a recursive one never ends.
+        let mut unwrapped = HashSet::new();
+        let valid = loop {
+            let name = match key.as_ref() {
+                ElmType::NamedType(name, _) => name.clone(),
+                key => break key.is_valid_map_key(),
+            };
+            match map_keys.get(&name) {
+                Some(MapKey::Newtype(inner)) if unwrapped.insert(name) => **key = inner.clone(),
+                Some(MapKey::Newtype(_)) | Some(MapKey::Invalid) => break false,
+                // Types we don't know are reported as undefined elsewhere.
+                Some(MapKey::Valid) | None => break true,
+            }
+        };
+        if !valid && result.is_ok() {
+            result = Err(TypeGenError::InvalidMapKey {
+                key: original,
+                referenced_by: referenced_by.0.clone(),
+            });
+        }
+    });
+    result
+}
+
+fn resolve_known_keys(
+    fields: &mut [ElmField],
+    mut keys: Vec<String>,
+    structs: &HashMap<Identifier, ElmStruct>,
+) {
+    keys.extend(used_keys(fields, structs));
+    for field in fields.iter_mut() {
+        if let Some(Flatten::Map { known_keys }) = &mut field.flatten {
+            *known_keys = keys.clone();
+        }
+    }
+}
+
+/// JSON keys written by these fields, following flattened structs.
+fn used_keys(fields: &[ElmField], structs: &HashMap<Identifier, ElmStruct>) -> Vec<String> {
+    let mut keys = vec![];
+    for field in fields {
+        match (&field.flatten, &field.ty) {
+            (None, _) => keys.extend(field.keys()),
+            (Some(Flatten::Struct), ElmType::NamedType(name, _)) => {
+                if let Some(StructFields::Named(inner)) = structs.get(name).map(|s| &s.fields) {
+                    keys.extend(used_keys(inner, structs));
+                }
+            }
+            _ => {}
+        }
+    }
+    keys
+}
+
+/// Names of the generic type parameters. Lifetimes and const generics are
+/// left out, they don't show up in the JSON.
+fn type_parameters(generics: &syn::Generics) -> Vec<Identifier> {
+    generics
+        .type_params()
+        .map(|param| Identifier(param.ident.to_string()))
+        .collect()
+}
+
+fn elm_type_from_type(ty: &syn::Type, scope: &Scope) -> Result<ElmType> {
     let ty = match &ty {
-        syn::Type::Array(_) => todo!("Array missing"),
-        syn::Type::BareFn(_) => todo!("BareFn missing"),
-        syn::Type::Group(_) => todo!("Group missing"),
-        syn::Type::ImplTrait(_) => todo!("ImplTrait missing"),
-        syn::Type::Infer(_) => todo!("Infer missing"),
-        syn::Type::Macro(_) => todo!("Macro missing"),
-        syn::Type::Never(_) => todo!("Never missing"),
-        syn::Type::Paren(_) => todo!("Paren missing"),
-        syn::Type::Path(type_path) => ElmType::from_identifier(type_path)?,
-        syn::Type::Ptr(_) => todo!("Ptr missing"),
-        syn::Type::Reference(_) => todo!("Reference missing"),
-        syn::Type::Slice(_) => todo!("Slice missing"),
-        syn::Type::TraitObject(_) => todo!("TraitObject missing"),
-        syn::Type::Tuple(_) => todo!("Tuple missing"),
-        syn::Type::Verbatim(_) => todo!("Verbatim missing"),
-        syn::Type::__TestExhaustive(_) => todo!("__TestExhaustive missing"),
+        syn::Type::Array(array) => {
+            let inner = Box::new(elm_type_from_type(&array.elem, scope)?);
+            match array_length(&array.len) {
+                Some(length) if scope.options.check_array_length => ElmType::Array(inner, length),
+                _ => ElmType::List(inner),
+            }
+        }
+        syn::Type::Paren(paren) => elm_type_from_type(&paren.elem, scope)?,
+        syn::Type::Path(type_path) => ElmType::from_identifier(type_path, scope)?,
+        syn::Type::Reference(reference) => elm_type_from_type(&reference.elem, scope)?,
+        syn::Type::Slice(slice) => ElmType::List(Box::new(elm_type_from_type(&slice.elem, scope)?)),
+        syn::Type::Tuple(tuple) if tuple.elems.is_empty() => ElmType::Unit,
+        syn::Type::Tuple(tuple) => {
+            let mut elements = vec![];
+            for element in &tuple.elems {
+                elements.push(elm_type_from_type(element, scope)?);
+            }
+            ElmType::Tuple(elements)
+        }
+        _ => {
+            let message = "This kind of type is not supported";
+            return Err(syn::Error::new_spanned(ty, message).into());
+        }
     };
     Ok(ty)
 }
 
+/// The length of an array type, if it is given as a literal.
+fn array_length(length: &syn::Expr) -> Option<usize> {
+    if let syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Int(length),
+        ..
+    }) = length
+    {
+        length.base10_parse().ok()
+    } else {
+        None
+    }
+}
+
+/// `io::Result` for the path of `io::Result<T>`.
+fn path_to_string(path: &syn::Path) -> String {
+    let segments: Vec<String> = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    segments.join("::")
+}
+
 fn last_path(path: &syn::Path) -> Result<Identifier> {
     let last = path.segments.last();
     if let Some(last) = last {
@@ -613,11 +2780,36 @@ mod tests {
     use std::io::Read;
 
     // Keeping this code as a module makes my editor typecheck them & run tests.
+    mod adjacently_tagged;
+    mod aliases;
+    mod c_like_enums;
+    mod conversions;
+    mod derive;
+    mod dicts;
+    mod events;
+    mod flatten;
+    mod floats;
+    mod from_only;
+    mod generics;
+    mod integer_enums;
+    mod internally_tagged;
+    mod maps;
     mod maybe;
     mod message;
+    mod module_paths;
+    mod module_tree;
+    mod newtypes;
+    mod optional_fields;
     mod person;
+    mod pointers;
     mod primitives;
+    mod renames;
+    mod results;
+    mod sequences;
+    mod tuples;
+    mod untagged;
     mod vectors;
+    mod whole_crate;
 
     #[test]
     fn basic_export() {
@@ -649,10 +2841,11 @@ mod tests {
     fn test_struct() {
         let ty = ElmStruct {
             name: Identifier("Person".to_string()),
-            fields: vec![
-                (Identifier("age".to_string()), ElmType::Int),
-                (Identifier("surname".to_string()), ElmType::String),
-            ],
+            generics: vec![],
+            fields: StructFields::Named(vec![
+                ElmField::new(Identifier("age".to_string()), ElmType::Int),
+                ElmField::new(Identifier("surname".to_string()), ElmType::String),
+            ]),
         };
         assert_eq!(ty.type_ref(), "Person");
         assert_eq!(
@@ -790,6 +2983,24 @@ mod tests {
         assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
     }
 
+    #[test]
+    fn test_undefined_reference() {
+        let ast = syn::parse_file(indoc! {"
+            struct WeatherData {
+                position: Coordinate,
+            }
+
+            impl ElmExport for WeatherData {}
+        "})
+        .expect("Failed to parse file");
+
+        let error = RustFile::parse(&ast).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Type Coordinate is referenced by WeatherData but not defined in the input"
+        );
+    }
+
     #[test]
     fn test_vectors() {
         let rust_file = parse_rust_file_for_test("src/tests/vectors.rs");
@@ -804,6 +3015,64 @@ mod tests {
         assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
     }
 
+    #[test]
+    fn test_missing_type_arguments() {
+        let ast = syn::parse_file(indoc! {r#"
+            struct Inventory {
+                items: Vec,
+            }
+
+            impl ElmExport for Inventory {}
+        "#})
+        .expect("Failed to parse file");
+        let error = RustFile::parse(&ast).unwrap_err();
+        assert_eq!(error.to_string(), "Vec needs a type argument");
+
+        let ast = syn::parse_file(indoc! {r#"
+            struct Inventory {
+                items: HashMap<String>,
+            }
+
+            impl ElmExport for Inventory {}
+        "#})
+        .expect("Failed to parse file");
+        let error = RustFile::parse(&ast).unwrap_err();
+        assert_eq!(error.to_string(), "HashMap needs two type arguments");
+
+        let ast = syn::parse_file(indoc! {r#"
+            struct Inventory {
+                items: Box<Fn(u32) -> u32>,
+            }
+
+            impl ElmExport for Inventory {}
+        "#})
+        .expect("Failed to parse file");
+        let error = RustFile::parse(&ast).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parenthesized arguments are not supported"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_types() {
+        for field in [
+            "Box<dyn Fn()>",
+            "*const u8",
+            "fn(u32) -> u32",
+            "impl Fn()",
+            "!",
+        ] {
+            let source = format!(
+                "struct Callbacks {{ field: {} }}\nimpl ElmExport for Callbacks {{}}",
+                field
+            );
+            let ast = syn::parse_file(&source).expect("Failed to parse file");
+            let error = RustFile::parse(&ast).unwrap_err();
+            assert_eq!(error.to_string(), "This kind of type is not supported");
+        }
+    }
+
     #[test]
     fn test_maybe() {
         let rust_file = parse_rust_file_for_test("src/tests/maybe.rs");
@@ -817,4 +3086,880 @@ mod tests {
 
         assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
     }
+
+    #[test]
+    fn test_floats() {
+        let rust_file = parse_rust_file_for_test("src/tests/floats.rs");
+        let elm_file_content = read_file_for_test("src/tests/Floats.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Floats".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_floats_lenient() {
+        let ast = syn::parse_file(&read_file_for_test("src/tests/floats.rs"))
+            .expect("Failed to parse file");
+        let options = TypeGenOptions {
+            float_mode: FloatMode::Lenient,
+            ..Default::default()
+        };
+        let rust_file = RustFile::parse_with_options(&ast, &options).expect("Failed to parse file");
+        let elm_file_content = read_file_for_test("src/tests/FloatsLenient.elm");
+
+        let elm_file_object = ElmFile {
+            name: "FloatsLenient".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_dicts() {
+        let rust_file = parse_rust_file_for_test("src/tests/dicts.rs");
+        let elm_file_content = read_file_for_test("src/tests/Dicts.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Dicts".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_maps_with_non_string_keys() {
+        let rust_file = parse_rust_file_for_test("src/tests/maps.rs");
+        let elm_file_content = read_file_for_test("src/tests/Maps.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Maps".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_invalid_map_key() {
+        let ast = syn::parse_file(indoc! {r#"
+            struct Point {
+                x: i32,
+                y: i32,
+            }
+
+            struct Grid {
+                cells: HashMap<Point, String>,
+            }
+
+            impl ElmExport for Grid {}
+        "#})
+        .expect("Failed to parse file");
+
+        let error = RustFile::parse(&ast).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Type Point is used as a map key by Grid, but it isn't written as a string, integer or bool"
+        );
+
+        // Floats are neither `Eq` nor `Ord`, so they are never map keys.
+        let ast = syn::parse_file(indoc! {r#"
+            struct Weights {
+                by_height: BTreeMap<f64, u32>,
+            }
+
+            impl ElmExport for Weights {}
+        "#})
+        .expect("Failed to parse file");
+
+        let error = RustFile::parse(&ast).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Map keys must serialize as strings, integers, bools or enum variants"
+        );
+    }
+
+    #[test]
+    fn test_tuples() {
+        let rust_file = parse_rust_file_for_test("src/tests/tuples.rs");
+        let elm_file_content = read_file_for_test("src/tests/Tuples.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Tuples".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_sequences() {
+        let rust_file = parse_rust_file_for_test("src/tests/sequences.rs");
+        let elm_file_content = read_file_for_test("src/tests/Sequences.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Sequences".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_checked_array_length() {
+        let ast = syn::parse_file(&read_file_for_test("src/tests/sequences.rs"))
+            .expect("Failed to parse file");
+        let options = TypeGenOptions {
+            check_array_length: true,
+            ..Default::default()
+        };
+        let rust_file = RustFile::parse_with_options(&ast, &options).expect("Failed to parse file");
+
+        let fixed = rust_file.export_structs[0].field_types()[0];
+        assert_eq!(fixed.type_ref(), "(List Int)");
+        assert_eq!(
+            fixed.decoder_ref(),
+            "(decodeListOfLength 3 Json.Decode.int)"
+        );
+        assert_eq!(fixed.encoder_ref(), "(Json.Encode.list Json.Encode.int)");
+
+        let elm_file_object = ElmFile {
+            name: "Sequences".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+        assert!(elm_file_object
+            .generate_file_content()
+            .contains("\ndecodeListOfLength : Int -> Json.Decode.Decoder a"));
+    }
+
+    #[test]
+    fn test_pointers() {
+        let rust_file = parse_rust_file_for_test("src/tests/pointers.rs");
+        let elm_file_content = read_file_for_test("src/tests/Pointers.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Pointers".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_results() {
+        let rust_file = parse_rust_file_for_test("src/tests/results.rs");
+        let elm_file_content = read_file_for_test("src/tests/Results.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Results".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_result_alias() {
+        let ast = syn::parse_file(indoc! {r#"
+            struct Job {
+                outcome: anyhow::Result<u32>,
+            }
+
+            impl ElmExport for Job {}
+        "#})
+        .expect("Failed to parse file");
+
+        let error = RustFile::parse(&ast).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "anyhow::Result is an alias for a Result with a fixed error type, which is not supported"
+        );
+    }
+
+    #[test]
+    fn test_generics() {
+        let rust_file = parse_rust_file_for_test("src/tests/generics.rs");
+        let elm_file_content = read_file_for_test("src/tests/Generics.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Generics".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_newtypes() {
+        let ast = syn::parse_file(&read_file_for_test("src/tests/newtypes.rs"))
+            .expect("Failed to parse file");
+        let options = TypeGenOptions {
+            opaque_newtypes: ["Email", "Tagged"].map(String::from).into(),
+            ..Default::default()
+        };
+        let rust_file = RustFile::parse_with_options(&ast, &options).expect("Failed to parse file");
+        let elm_file_content = read_file_for_test("src/tests/Newtypes.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Newtypes".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_events() {
+        let rust_file = parse_rust_file_for_test("src/tests/events.rs");
+        let elm_file_content = read_file_for_test("src/tests/Events.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Events".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_renames() {
+        let rust_file = parse_rust_file_for_test("src/tests/renames.rs");
+        let elm_file_content = read_file_for_test("src/tests/Renames.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Renames".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_internally_tagged() {
+        let rust_file = parse_rust_file_for_test("src/tests/internally_tagged.rs");
+        let elm_file_content = read_file_for_test("src/tests/InternallyTagged.elm");
+
+        let elm_file_object = ElmFile {
+            name: "InternallyTagged".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_internally_tagged_tuple_variant() {
+        let ast = syn::parse_file(indoc! {r#"
+            #[serde(tag = "type")]
+            enum Shape {
+                Point(i32, i32),
+            }
+
+            impl ElmExport for Shape {}
+        "#})
+        .expect("Failed to parse file");
+
+        let error = RustFile::parse(&ast).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Variant Point of Shape can't be serialized: internally tagged enums can't contain tuple variants"
+        );
+    }
+
+    #[test]
+    fn test_adjacently_tagged() {
+        let rust_file = parse_rust_file_for_test("src/tests/adjacently_tagged.rs");
+        let elm_file_content = read_file_for_test("src/tests/AdjacentlyTagged.elm");
+
+        let elm_file_object = ElmFile {
+            name: "AdjacentlyTagged".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_untagged() {
+        let rust_file = parse_rust_file_for_test("src/tests/untagged.rs");
+        let elm_file_content = read_file_for_test("src/tests/Untagged.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Untagged".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_untagged_warnings() {
+        let rust_file = parse_rust_file_for_test("src/tests/untagged.rs");
+        assert_eq!(
+            rust_file.warnings,
+            [
+                "Variant Small of untagged enum Value can never be decoded, because variant Count before it accepts the same JSON",
+                "Variant Employee of untagged enum Value can never be decoded, because variant Person before it accepts the same JSON",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_untagged_field_types() {
+        // Both have a value, but B's can't be decoded as A's.
+        let ast = syn::parse_file(indoc! {r#"
+            #[serde(untagged)]
+            enum V {
+                A { value: i32 },
+                B { value: String },
+                C { value: u8 },
+            }
+
+            impl ElmExport for V {}
+        "#})
+        .expect("Failed to parse file");
+
+        let rust_file = RustFile::parse(&ast).unwrap();
+        assert_eq!(
+            rust_file.warnings,
+            ["Variant C of untagged enum V can never be decoded, because variant A before it accepts the same JSON"]
+        );
+    }
+
+    #[test]
+    fn test_optional_fields() {
+        let rust_file = parse_rust_file_for_test("src/tests/optional_fields.rs");
+        let elm_file_content = read_file_for_test("src/tests/OptionalFields.elm");
+
+        let elm_file_object = ElmFile {
+            name: "OptionalFields".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_flatten() {
+        let rust_file = parse_rust_file_for_test("src/tests/flatten.rs");
+        let elm_file_content = read_file_for_test("src/tests/Flatten.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Flatten".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let rust_file = parse_rust_file_for_test("src/tests/conversions.rs");
+        let elm_file_content = read_file_for_test("src/tests/Conversions.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Conversions".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_from_only() {
+        let rust_file = parse_rust_file_for_test("src/tests/from_only.rs");
+        let elm_file_content = read_file_for_test("src/tests/FromOnly.elm");
+
+        let elm_file_object = ElmFile {
+            name: "FromOnly".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_conversion_to_different_types() {
+        let ast = syn::parse_file(indoc! {r#"
+            #[serde(from = "String", into = "u32")]
+            struct Code {
+                value: u32,
+            }
+
+            impl ElmExport for Code {}
+        "#})
+        .expect("Failed to parse file");
+
+        let error = RustFile::parse(&ast).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Converting from and into different types is not supported"
+        );
+    }
+
+    #[test]
+    fn test_aliases() {
+        let rust_file = parse_rust_file_for_test("src/tests/aliases.rs");
+        let elm_file_content = read_file_for_test("src/tests/Aliases.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Aliases".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_c_like_enums() {
+        let rust_file = parse_rust_file_for_test("src/tests/c_like_enums.rs");
+        let elm_file_content = read_file_for_test("src/tests/CLikeEnums.elm");
+
+        let elm_file_object = ElmFile {
+            name: "CLikeEnums".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_integer_enums() {
+        let rust_file = parse_rust_file_for_test("src/tests/integer_enums.rs");
+        let elm_file_content = read_file_for_test("src/tests/IntegerEnums.elm");
+
+        let elm_file_object = ElmFile {
+            name: "IntegerEnums".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_repr_alone_is_not_integer() {
+        let ast = syn::parse_file(indoc! {r#"
+            #[repr(u8)]
+            enum Plain {
+                A = 3,
+            }
+
+            #[derive(Clone, Serialize)]
+            #[repr(u8)]
+            enum Derived {
+                B = 4,
+            }
+
+            #[derive(Serialize)]
+            #[repr(u8)]
+            enum Op {
+                Push(u8),
+                Pop,
+            }
+
+            #[elm(integer)]
+            enum Marked {
+                C = 5,
+            }
+
+            struct Flags {
+                plain: Plain,
+                derived: Derived,
+                op: Op,
+                marked: Marked,
+            }
+
+            impl ElmExport for Flags {}
+        "#})
+        .expect("Failed to parse file");
+
+        let rust_file = RustFile::parse(&ast).unwrap();
+        let encoders: Vec<String> = rust_file
+            .export_enums
+            .iter()
+            .map(|enum_| enum_.encoder_def())
+            .collect();
+        assert!(encoders[0].contains("Json.Encode.string \"A\""));
+        assert!(encoders[1].contains("Json.Encode.string \"B\""));
+        assert!(encoders[2].contains("Json.Encode.string \"Pop\""));
+        assert!(encoders[3].contains("Json.Encode.int 5"));
+    }
+
+    #[test]
+    fn test_serde_repr() {
+        let ast = syn::parse_file(indoc! {r#"
+            #[derive(Serialize_repr, Deserialize_repr)]
+            #[repr(u8)]
+            enum Answer {
+                No,
+                Yes = 4,
+            }
+
+            impl ElmExport for Answer {}
+        "#})
+        .expect("Failed to parse file");
+
+        let rust_file = RustFile::parse(&ast).unwrap();
+        let decoder = rust_file.export_enums[0].decoder_def();
+        assert!(decoder.contains(
+            "                    0 ->\n                        Json.Decode.succeed No\n"
+        ));
+        assert!(decoder.contains(
+            "                    4 ->\n                        Json.Decode.succeed Yes\n"
+        ));
+    }
+
+    #[test]
+    fn test_serde_repr_with_fields() {
+        let ast = syn::parse_file(indoc! {r#"
+            #[derive(serde_repr::Serialize_repr)]
+            #[repr(u8)]
+            enum Answer {
+                No,
+                Maybe(u8),
+            }
+
+            impl ElmExport for Answer {}
+        "#})
+        .expect("Failed to parse file");
+
+        let error = RustFile::parse(&ast).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Variant Maybe of Answer can't be serialized: enums with an integer representation can only have unit variants"
+        );
+    }
+
+    #[test]
+    fn test_derive() {
+        let rust_file = parse_rust_file_for_test("src/tests/derive.rs");
+        let elm_file_content = read_file_for_test("src/tests/Derive.elm");
+
+        let elm_file_object = ElmFile {
+            name: "Derive".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_unknown_elm_attribute() {
+        let ast = syn::parse_file(indoc! {r#"
+            #[derive(ElmExport)]
+            #[elm(transparent)]
+            struct UserId(u64);
+        "#})
+        .expect("Failed to parse file");
+
+        let error = RustFile::parse(&ast).unwrap_err();
+        assert_eq!(error.to_string(), "Unknown elm attribute");
+    }
+
+    #[test]
+    fn test_elm_type_of_std_types() {
+        use std::collections::BTreeMap;
+        type Table<'a> = BTreeMap<String, Vec<Option<(u8, &'a str)>>>;
+        let row = ElmType::Tuple(vec![ElmType::Int, ElmType::String]);
+        let expected = ElmType::Dict(
+            Box::new(ElmType::String),
+            Box::new(ElmType::List(Box::new(ElmType::Maybe(Box::new(row))))),
+        );
+        assert_eq!(Table::elm_type(), expected);
+        assert_eq!(
+            <Box<[f64]>>::elm_type(),
+            ElmType::List(Box::new(ElmType::Float))
+        );
+        assert_eq!(
+            <[char; 2]>::elm_type(),
+            ElmType::Array(Box::new(ElmType::String), 2)
+        );
+    }
+
+    #[test]
+    fn test_whole_crate() {
+        let root = Path::new("src/tests/whole_crate/mod.rs");
+        let rust_file = RustFile::parse_crate(root, &TypeGenOptions::default()).unwrap();
+        let elm_file_content = read_file_for_test("src/tests/WholeCrate.elm");
+
+        let elm_file_object = ElmFile {
+            name: "WholeCrate".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_module_paths() {
+        let root = Path::new("src/tests/module_paths/mod.rs");
+        let rust_file = RustFile::parse_crate(root, &TypeGenOptions::default()).unwrap();
+        let elm_file_content = read_file_for_test("src/tests/ModulePaths.elm");
+
+        let elm_file_object = ElmFile {
+            name: "ModulePaths".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+
+        assert_eq!(elm_file_object.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_module_tree() {
+        let root = Path::new("src/tests/module_tree/mod.rs");
+        let rust_file = RustFile::parse_crate(root, &TypeGenOptions::default()).unwrap();
+        let elm_files = rust_file.elm_modules("Api").unwrap();
+
+        let names: Vec<&str> = elm_files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["Api", "Api.Game", "Api.Game.Cards", "Api.Shop"]);
+        for elm_file in elm_files {
+            let path = Path::new("src/tests/ModuleTree").join(elm_file.file_path());
+            let elm_file_content = read_file_for_test(path.to_str().unwrap());
+            assert_eq!(elm_file.generate_file_content(), elm_file_content);
+        }
+    }
+
+    #[test]
+    fn test_module_tree_import_cycle() {
+        // game::Player uses shop::Card, which uses game::Suit.
+        let root = Path::new("src/tests/module_paths/mod.rs");
+        let rust_file = RustFile::parse_crate(root, &TypeGenOptions::default()).unwrap();
+        let error = rust_file.elm_modules("Api").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Elm modules can't import each other in a cycle: Api.Game -> Api.Shop -> Api.Game"
+        );
+    }
+
+    #[test]
+    fn test_unexported_types_are_not_converted() {
+        let source = indoc! {r#"
+            mod ffi {
+                pub struct Callbacks {
+                    pub on_done: Box<dyn Fn()>,
+                }
+
+                pub struct Raw {
+                    pub p: *const u8,
+                }
+
+                pub struct Grid {
+                    pub cells: std::collections::HashMap<(u8, u8), u8>,
+                }
+
+                #[derive(Serialize_repr)]
+                #[repr(u8)]
+                pub enum Op {
+                    Push(u8),
+                    Pop,
+                }
+            }
+
+            mod model {
+                pub struct Card {
+                    pub name: String,
+                }
+            }
+
+            struct Deck {
+                cards: Vec<model::Card>,
+            }
+
+            impl ElmExport for Deck {}
+        "#};
+        let ast = syn::parse_file(source).expect("Failed to parse file");
+        let rust_file = RustFile::parse(&ast).unwrap();
+        let names: Vec<&str> = (rust_file.export_structs.iter())
+            .map(|s| s.name.0.as_str())
+            .collect();
+        assert_eq!(names, ["Deck", "Card"]);
+
+        // Once exported, the error is reported.
+        let source = source.replace("cards: Vec<model::Card>,", "raw: ffi::Raw,");
+        let ast = syn::parse_file(&source).expect("Failed to parse file");
+        let error = RustFile::parse(&ast).unwrap_err();
+        assert_eq!(error.to_string(), "This kind of type is not supported");
+    }
+
+    #[test]
+    fn test_module_prefix_only_for_exported_names() {
+        let ast = syn::parse_file(indoc! {r#"
+            mod game {
+                pub struct Card {
+                    pub suit: String,
+                }
+            }
+
+            mod cache {
+                struct Card {
+                    raw: Vec<u8>,
+                }
+            }
+
+            struct Table {
+                cards: Vec<game::Card>,
+            }
+
+            impl ElmExport for Table {}
+        "#})
+        .expect("Failed to parse file");
+        let rust_file = RustFile::parse(&ast).unwrap();
+        let names: Vec<&str> = (rust_file.export_structs.iter())
+            .map(|s| s.name.0.as_str())
+            .collect();
+        assert_eq!(names, ["Table", "Card"]);
+        assert_eq!(
+            rust_file.definition_paths[&Identifier::new("Card")],
+            ["game", "Card"]
+        );
+    }
+
+    #[test]
+    fn test_module_prefix_collision() {
+        let ast = syn::parse_file(indoc! {r#"
+            mod a {
+                pub struct Foo {
+                    pub x: u8,
+                }
+            }
+
+            mod b {
+                pub struct Foo {
+                    pub y: u8,
+                }
+            }
+
+            struct AFoo {
+                z: u8,
+            }
+
+            struct Both {
+                a: a::Foo,
+                b: b::Foo,
+                c: AFoo,
+            }
+
+            impl ElmExport for Both {}
+        "#})
+        .expect("Failed to parse file");
+        let error = RustFile::parse(&ast).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Types a::Foo and AFoo would both be called AFoo in elm"
+        );
+    }
+
+    #[test]
+    fn test_crate_and_super_paths() {
+        let ast = syn::parse_file(indoc! {r#"
+            mod model {
+                pub struct Card {
+                    pub id: crate::ids::Id,
+                    pub owner: super::Player,
+                }
+            }
+
+            mod ids {
+                pub struct Id(u32);
+            }
+
+            struct Player {
+                cards: Vec<self::model::Card>,
+            }
+
+            // Defined here, not the one from std.
+            struct Result {
+                won: bool,
+            }
+
+            struct Game {
+                first: ::std::result::Result<Player, String>,
+                second: crate::Result,
+            }
+
+            impl ElmExport for Game {}
+        "#})
+        .expect("Failed to parse file");
+
+        let rust_file = RustFile::parse(&ast).unwrap();
+        let mut names: Vec<&str> = rust_file
+            .export_structs
+            .iter()
+            .map(|s| s.name.0.as_str())
+            .collect();
+        names.sort_unstable();
+        assert_eq!(names, ["Card", "Game", "Id", "Player", "Result"]);
+        let game = &rust_file.all_structs[&Identifier::new("Game")];
+        let StructFields::Named(fields) = &game.fields else {
+            panic!("Game has named fields");
+        };
+        assert_eq!(
+            fields[0].ty,
+            ElmType::Result(
+                Box::new(ElmType::NamedType(Identifier::new("Player"), vec![])),
+                Box::new(ElmType::String)
+            )
+        );
+        assert_eq!(
+            fields[1].ty,
+            ElmType::NamedType(Identifier::new("Result"), vec![])
+        );
+        // Both get a decoder and an encoder, which must not clash.
+        let elm_file = ElmFile {
+            name: "Out".to_string(),
+            structs: rust_file.export_structs,
+            enums: rust_file.export_enums,
+        };
+        let content = elm_file.generate_file_content();
+        for definition in [
+            "decodeResult :",
+            "encodeResult :",
+            "decodeStdResult :",
+            "encodeStdResult :",
+        ] {
+            assert_eq!(content.matches(definition).count(), 1, "{}", definition);
+        }
+        assert!(content
+            .contains("    { first : (Result.Result String Player)\n    , second : Result\n"));
+    }
+
+    #[test]
+    fn test_missing_module() {
+        let root = Path::new("src/tests/missing/mod.rs");
+        let error = RustFile::parse_crate(root, &TypeGenOptions::default()).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Failed to read src/tests/missing/mod.rs: "));
+    }
 }
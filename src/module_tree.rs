@@ -0,0 +1,156 @@
+//! One elm module for each rust module, instead of a single file with all
+//! exported types. `crate::game::cards` becomes `Api.Game.Cards` and types
+//! from other modules are referred to by their qualified names.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+
+use crate::attributes::RenameRule;
+use crate::{ElmFile, ElmType, Identifier, Result, RustFile, TypeGenError};
+
+impl RustFile {
+    /// The exported types, grouped into one elm module for each rust module
+    /// below `prefix`. Types of the crate root go into `prefix` itself.
+    pub fn elm_modules(&self, prefix: &str) -> Result<Vec<ElmFile>> {
+        // The elm module of each type and its name inside of that module.
+        let mut locations: HashMap<Identifier, (String, Identifier)> = HashMap::new();
+        let exported = (self.export_structs.iter().map(|s| &s.name))
+            .chain(self.export_enums.iter().map(|e| &e.name));
+        for name in exported {
+            let location = match self.definition_paths.get(name) {
+                Some(path) => {
+                    let (local_name, module) = path.split_last().unwrap();
+                    (module_name(prefix, module), Identifier(local_name.clone()))
+                }
+                None => (prefix.to_string(), name.clone()),
+            };
+            locations.insert(name.clone(), location);
+        }
+
+        let mut files: BTreeMap<String, ElmFile> = BTreeMap::new();
+        for struct_ in &self.export_structs {
+            let (module, local_name) = &locations[&struct_.name];
+            let mut struct_ = struct_.clone();
+            struct_.name = local_name.clone();
+            for ty in struct_.field_types_mut() {
+                qualify(ty, module, &locations);
+            }
+            file_for(&mut files, module).structs.push(struct_);
+        }
+        for enum_ in &self.export_enums {
+            let (module, local_name) = &locations[&enum_.name];
+            let mut enum_ = enum_.clone();
+            enum_.name = local_name.clone();
+            for ty in enum_.field_types_mut() {
+                qualify(ty, module, &locations);
+            }
+            file_for(&mut files, module).enums.push(enum_);
+        }
+
+        check_import_cycles(&files)?;
+        Ok(files.into_values().collect())
+    }
+}
+
+impl ElmFile {
+    /// Where the module lives below the source directory, `Api/Game.elm`
+    /// for `Api.Game`.
+    pub fn file_path(&self) -> PathBuf {
+        PathBuf::from(format!("{}.elm", self.name.replace('.', "/")))
+    }
+
+    /// Other generated modules that types in this file refer to.
+    pub(crate) fn imported_modules(&self) -> BTreeSet<String> {
+        let mut modules = BTreeSet::new();
+        for ty in self.field_types() {
+            ty.walk(&mut |ty| {
+                if let ElmType::NamedType(name, _) = ty {
+                    if let Some(module) = name.module() {
+                        modules.insert(module.to_string());
+                    }
+                }
+            });
+        }
+        modules.remove(&self.name);
+        modules
+    }
+}
+
+fn file_for<'a>(files: &'a mut BTreeMap<String, ElmFile>, module: &str) -> &'a mut ElmFile {
+    files.entry(module.to_string()).or_insert_with(|| ElmFile {
+        name: module.to_string(),
+        structs: vec![],
+        enums: vec![],
+    })
+}
+
+/// `Api.Game.Cards` for `["game", "cards"]`.
+fn module_name(prefix: &str, path: &[String]) -> String {
+    let mut name = prefix.to_string();
+    for segment in path {
+        name.push('.');
+        name.push_str(&RenameRule::Pascal.apply_to_field(segment));
+    }
+    name
+}
+
+/// Names types from `module` by their local name and all others by their
+/// qualified name.
+fn qualify(ty: &mut ElmType, module: &str, locations: &HashMap<Identifier, (String, Identifier)>) {
+    ty.walk_mut(&mut |ty| {
+        if let ElmType::NamedType(name, _) = ty {
+            if let Some((type_module, local_name)) = locations.get(name) {
+                *name = if type_module == module {
+                    local_name.clone()
+                } else {
+                    Identifier(format!("{}.{}", type_module, local_name.0))
+                };
+            }
+        }
+    });
+}
+
+/// Elm rejects modules that import each other, even indirectly.
+fn check_import_cycles(files: &BTreeMap<String, ElmFile>) -> Result<()> {
+    let imports: BTreeMap<&str, BTreeSet<String>> = files
+        .iter()
+        .map(|(name, file)| (name.as_str(), file.imported_modules()))
+        .collect();
+    let mut finished = BTreeSet::new();
+    for module in imports.keys() {
+        let mut stack = vec![module.to_string()];
+        if let Some(cycle) = find_cycle(&imports, &mut stack, &mut finished) {
+            return Err(TypeGenError::ImportCycle {
+                cycle: cycle.join(" -> "),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Depth first search from the last module on the stack.
+fn find_cycle(
+    imports: &BTreeMap<&str, BTreeSet<String>>,
+    stack: &mut Vec<String>,
+    finished: &mut BTreeSet<String>,
+) -> Option<Vec<String>> {
+    let module = stack.last().unwrap().clone();
+    if finished.contains(&module) {
+        return None;
+    }
+    for imported in imports.get(module.as_str()).into_iter().flatten() {
+        if let Some(start) = stack.iter().position(|m| m == imported) {
+            let mut cycle = stack[start..].to_vec();
+            cycle.push(imported.clone());
+            return Some(cycle);
+        }
+        stack.push(imported.clone());
+        let cycle = find_cycle(imports, stack, finished);
+        stack.pop();
+        if cycle.is_some() {
+            return cycle;
+        }
+    }
+    finished.insert(module);
+    None
+}
@@ -0,0 +1,153 @@
+//! Reads a whole crate by following the `mod` declarations of its root file.
+//! Each module keeps its path, so types can be looked up by their paths.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use syn::{Lit, Meta};
+
+use crate::{Result, TypeGenError};
+
+/// The items of one module, without its submodules.
+#[derive(Debug)]
+pub struct RustModule {
+    /// Like `["model", "card"]` for `crate::model::card`, empty for the root.
+    pub path: Vec<String>,
+    pub items: Vec<syn::Item>,
+}
+
+/// The crate root, like `src/lib.rs`, and every module below it.
+pub fn load_crate(root: &Path) -> Result<Vec<RustModule>> {
+    let file = read_file(root)?;
+    let directory = parent(root);
+    let mut modules = vec![];
+    collect_modules(file.items, vec![], &directory, &directory, &mut modules)?;
+    Ok(modules)
+}
+
+/// A single file and the modules written inline in it. `mod foo;`
+/// declarations are not followed.
+pub fn inline_modules(items: Vec<syn::Item>, path: Vec<String>, output: &mut Vec<RustModule>) {
+    let mut module_items = vec![];
+    for item in items {
+        match item {
+            syn::Item::Mod(syn::ItemMod {
+                ident,
+                content: Some((_, items)),
+                ..
+            }) => {
+                let mut sub_path = path.clone();
+                sub_path.push(ident.to_string());
+                inline_modules(items, sub_path, output);
+            }
+            syn::Item::Mod(_) => {}
+            item => module_items.push(item),
+        }
+    }
+    output.push(RustModule {
+        path,
+        items: module_items,
+    });
+}
+
+/// Submodules are looked up in `directory`, while `#[path]` attributes are
+/// relative to `path_directory`. They differ for `foo.rs`, whose submodules
+/// live in `foo/`, but whose `#[path]` attributes are relative to its own
+/// directory.
+fn collect_modules(
+    items: Vec<syn::Item>,
+    path: Vec<String>,
+    directory: &Path,
+    path_directory: &Path,
+    output: &mut Vec<RustModule>,
+) -> Result<()> {
+    let mut module_items = vec![];
+    for item in items {
+        let item_mod = match item {
+            syn::Item::Mod(item_mod) => item_mod,
+            item => {
+                module_items.push(item);
+                continue;
+            }
+        };
+        let name = item_mod.ident.to_string();
+        let mut sub_path = path.clone();
+        sub_path.push(name.clone());
+        let path_attribute = path_attribute(&item_mod.attrs)?;
+        match item_mod.content {
+            // mod foo { ... }
+            Some((_, items)) => {
+                let directory = match &path_attribute {
+                    Some(path) => directory.join(path),
+                    None => directory.join(&name),
+                };
+                collect_modules(items, sub_path, &directory, &directory, output)?;
+            }
+            // mod foo;
+            None => {
+                let file_path = match &path_attribute {
+                    Some(path) => path_directory.join(path),
+                    None => {
+                        let file_path = directory.join(format!("{}.rs", name));
+                        if file_path.exists() {
+                            file_path
+                        } else {
+                            directory.join(&name).join("mod.rs")
+                        }
+                    }
+                };
+                let file = read_file(&file_path)?;
+                // Files named mod.rs or chosen with #[path] keep their
+                // submodules next to them.
+                let is_mod_rs = path_attribute.is_some()
+                    || file_path.file_name().is_some_and(|f| f == "mod.rs");
+                let file_directory = parent(&file_path);
+                let sub_directory = if is_mod_rs {
+                    file_directory.clone()
+                } else {
+                    directory.join(&name)
+                };
+                collect_modules(
+                    file.items,
+                    sub_path,
+                    &sub_directory,
+                    &file_directory,
+                    output,
+                )?;
+            }
+        }
+    }
+    output.push(RustModule {
+        path,
+        items: module_items,
+    });
+    Ok(())
+}
+
+fn read_file(path: &Path) -> Result<syn::File> {
+    let content = fs::read_to_string(path).map_err(|source| TypeGenError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(syn::parse_file(&content)?)
+}
+
+fn parent(path: &Path) -> PathBuf {
+    path.parent().map(Path::to_path_buf).unwrap_or_default()
+}
+
+/// The value of `#[path = "other.rs"]`.
+fn path_attribute(attrs: &[syn::Attribute]) -> Result<Option<String>> {
+    for attr in attrs {
+        if !attr.path.is_ident("path") {
+            continue;
+        }
+        if let Meta::NameValue(name_value) = attr.parse_meta()? {
+            if let Lit::Str(lit_str) = &name_value.lit {
+                return Ok(Some(lit_str.value()));
+            }
+        }
+        return Err(syn::Error::new_spanned(attr, "Expected #[path = \"...\"]").into());
+    }
+    Ok(None)
+}
@@ -0,0 +1,309 @@
+//! Resolves the paths of named types, like `model::Card` or
+//! `crate::model::Card`, to the struct or enum they refer to.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::attributes::RenameRule;
+use crate::modules::RustModule;
+use crate::{ElmType, Identifier, Result, TypeGenError};
+
+/// Imports that refer to each other in a cycle give up after this many steps.
+const MAX_DEPTH: usize = 8;
+
+/// Types and modules have their own namespaces, `mod card;` and
+/// `struct card;` can live next to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Namespace {
+    Type,
+    Module,
+}
+
+/// What a path refers to, by its full path from the crate root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Target {
+    Module(Vec<String>),
+    Definition(Vec<String>),
+}
+
+#[derive(Debug, Default)]
+struct ModuleNames {
+    /// Structs and enums defined in the module.
+    definitions: HashSet<String>,
+    /// `use a::b as c;` makes `c` refer to `a::b`.
+    imports: HashMap<String, Vec<String>>,
+    /// `use a::*;`
+    globs: Vec<Vec<String>>,
+}
+
+/// The names visible in every module of a crate.
+#[derive(Debug, Default)]
+pub struct NameTable {
+    modules: HashMap<Vec<String>, ModuleNames>,
+    /// Definitions are known by their full path, like `game::Card`, until
+    /// `elm_names` picks the names of the exported ones.
+    elm_names: HashMap<Vec<String>, Identifier>,
+    /// Types that `#[derive(ElmExport)]` described at runtime, by the name
+    /// that stands in for them in the source it passes along.
+    described: HashMap<Identifier, ElmType>,
+}
+
+/// How the path of a type was resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedPath {
+    /// A struct or enum of the crate, by its elm name.
+    Definition(Identifier),
+    /// Builtin types, type parameters and types we don't know, by the last
+    /// segment of the path.
+    Name(Identifier),
+}
+
+impl NameTable {
+    pub fn new(modules: &[RustModule]) -> NameTable {
+        let mut table = NameTable::default();
+        let mut definitions = vec![];
+        for module in modules {
+            let names = table.modules.entry(module.path.clone()).or_default();
+            for item in &module.items {
+                match item {
+                    syn::Item::Struct(item_struct) => {
+                        names.definitions.insert(item_struct.ident.to_string());
+                    }
+                    syn::Item::Enum(item_enum) => {
+                        names.definitions.insert(item_enum.ident.to_string());
+                    }
+                    // `use ::serde::Serialize;` is always another crate.
+                    syn::Item::Use(item_use) if item_use.leading_colon.is_none() => {
+                        collect_imports(&item_use.tree, &mut vec![], names);
+                    }
+                    _ => {}
+                }
+            }
+            for name in &names.definitions {
+                let mut path = module.path.clone();
+                path.push(name.clone());
+                definitions.push(path);
+            }
+        }
+        table.elm_names = definitions
+            .into_iter()
+            .map(|path| {
+                let name = Identifier(path.join("::"));
+                (path, name)
+            })
+            .collect();
+        table
+    }
+
+    /// Names for the source of a single item, where `described` types are
+    /// already known and everything else by the last segment of its path.
+    pub fn described(described: HashMap<Identifier, ElmType>) -> NameTable {
+        NameTable {
+            described,
+            ..NameTable::default()
+        }
+    }
+
+    /// The full path of a struct or enum, by the name it is known by
+    /// before `elm_names`.
+    pub fn definition_path(&self, name: &Identifier) -> Option<Vec<String>> {
+        self.elm_names
+            .iter()
+            .find(|(_, elm_name)| *elm_name == name)
+            .map(|(path, _)| path.clone())
+    }
+
+    /// Elm only has one namespace for the generated file, so `game::Card`
+    /// and `shop::Card` become `GameCard` and `ShopCard` when both are
+    /// exported. Unique names stay as they are. Returns the new name of
+    /// every exported definition.
+    pub fn elm_names(&self, exported: &[&Identifier]) -> Result<HashMap<Identifier, Identifier>> {
+        let paths: Vec<(&Identifier, Vec<String>)> = exported
+            .iter()
+            .filter_map(|name| Some((*name, self.definition_path(name)?)))
+            .collect();
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for (_, path) in &paths {
+            *counts.entry(path.last().unwrap()).or_default() += 1;
+        }
+        let mut result = HashMap::new();
+        let mut taken: HashMap<Identifier, &Identifier> = HashMap::new();
+        for (name, path) in &paths {
+            let (last, module) = path.split_last().unwrap();
+            let mut elm_name: String = if counts[last.as_str()] > 1 {
+                module
+                    .iter()
+                    .map(|segment| RenameRule::Pascal.apply_to_field(segment))
+                    .collect()
+            } else {
+                String::new()
+            };
+            elm_name.push_str(last);
+            let elm_name = Identifier(elm_name);
+            if let Some(other) = taken.insert(elm_name.clone(), name) {
+                return Err(TypeGenError::NameCollision {
+                    name: elm_name.0,
+                    first: other.0.clone(),
+                    second: name.0.clone(),
+                });
+            }
+            result.insert((*name).clone(), elm_name);
+        }
+        Ok(result)
+    }
+
+    /// The names visible inside the module at `path`.
+    pub fn scope<'a>(&'a self, path: &'a [String]) -> ModuleScope<'a> {
+        ModuleScope {
+            table: self,
+            module: path,
+        }
+    }
+
+    /// Resolves `segments` as written inside `module`.
+    fn resolve(
+        &self,
+        module: &[String],
+        segments: &[String],
+        namespace: Namespace,
+        depth: usize,
+    ) -> Option<Target> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let (last, init) = segments.split_last()?;
+        let mut current = module.to_vec();
+        for (i, segment) in init.iter().enumerate() {
+            match segment.as_str() {
+                "crate" if i == 0 => current.clear(),
+                "self" if i == 0 => {}
+                "super" => {
+                    current.pop()?;
+                }
+                _ => match self.member(&current, segment, Namespace::Module, depth)? {
+                    Target::Module(path) => current = path,
+                    Target::Definition(_) => return None,
+                },
+            }
+        }
+        self.member(&current, last, namespace, depth)
+    }
+
+    /// The item called `name` in `module`, either defined or imported there.
+    fn member(
+        &self,
+        module: &[String],
+        name: &str,
+        namespace: Namespace,
+        depth: usize,
+    ) -> Option<Target> {
+        let names = self.modules.get(module)?;
+        let mut path = module.to_vec();
+        path.push(name.to_string());
+        match namespace {
+            Namespace::Type if names.definitions.contains(name) => {
+                return Some(Target::Definition(path));
+            }
+            Namespace::Module if self.modules.contains_key(&path) => {
+                return Some(Target::Module(path));
+            }
+            _ => {}
+        }
+        // Imports are relative to the module that has them.
+        if let Some(target) = names.imports.get(name) {
+            return self.resolve(module, target, namespace, depth + 1);
+        }
+        names.globs.iter().find_map(|glob| {
+            let mut target = glob.clone();
+            target.push(name.to_string());
+            self.resolve(module, &target, namespace, depth + 1)
+        })
+    }
+}
+
+/// Remembers what the names of a `use` item refer to. `prefix` holds the
+/// segments of the enclosing `a::b::{...}`.
+fn collect_imports(tree: &syn::UseTree, prefix: &mut Vec<String>, names: &mut ModuleNames) {
+    match tree {
+        syn::UseTree::Path(use_path) => {
+            prefix.push(use_path.ident.to_string());
+            collect_imports(&use_path.tree, prefix, names);
+            prefix.pop();
+        }
+        syn::UseTree::Name(use_name) => {
+            // `use a::b::{self};` imports `b`.
+            if use_name.ident == "self" {
+                if let Some(last) = prefix.last() {
+                    names.imports.insert(last.clone(), prefix.clone());
+                }
+            } else {
+                let mut target = prefix.clone();
+                target.push(use_name.ident.to_string());
+                names.imports.insert(use_name.ident.to_string(), target);
+            }
+        }
+        syn::UseTree::Rename(use_rename) => {
+            // `use a::Trait as _;` doesn't add a name.
+            if use_rename.rename == "_" {
+                return;
+            }
+            let mut target = prefix.clone();
+            if use_rename.ident != "self" {
+                target.push(use_rename.ident.to_string());
+            }
+            names.imports.insert(use_rename.rename.to_string(), target);
+        }
+        syn::UseTree::Glob(_) => names.globs.push(prefix.clone()),
+        syn::UseTree::Group(use_group) => {
+            for tree in &use_group.items {
+                collect_imports(tree, prefix, names);
+            }
+        }
+    }
+}
+
+/// The names visible inside one module.
+#[derive(Debug, Clone, Copy)]
+pub struct ModuleScope<'a> {
+    table: &'a NameTable,
+    module: &'a [String],
+}
+
+impl<'a> ModuleScope<'a> {
+    /// The name of a struct or enum defined in this module, see `elm_names`.
+    pub fn elm_name(&self, ident: &syn::Ident) -> Identifier {
+        let mut path = self.module.to_vec();
+        path.push(ident.to_string());
+        match self.table.elm_names.get(&path) {
+            Some(elm_name) => elm_name.clone(),
+            None => Identifier(ident.to_string()),
+        }
+    }
+
+    /// The elm type of a path that stands in for a described type.
+    pub fn described_type(&self, path: &syn::Path) -> Option<&ElmType> {
+        let ident = path.get_ident()?;
+        self.table.described.get(&Identifier(ident.to_string()))
+    }
+
+    pub fn resolve(&self, path: &syn::Path) -> ResolvedPath {
+        let segments: Vec<String> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        let last = Identifier(segments.last().cloned().unwrap_or_default());
+        if path.leading_colon.is_some() {
+            return ResolvedPath::Name(last);
+        }
+        match self
+            .table
+            .resolve(self.module, &segments, Namespace::Type, 0)
+        {
+            Some(Target::Definition(path)) => match self.table.elm_names.get(&path) {
+                Some(elm_name) => ResolvedPath::Definition(elm_name.clone()),
+                None => ResolvedPath::Name(last),
+            },
+            _ => ResolvedPath::Name(last),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! The JSON values that the variants of an untagged enum accept.
+//! `Json.Decode.oneOf` picks the first variant that decodes, so a variant can
+//! never be decoded when an earlier one accepts everything it accepts.
+
+use std::collections::HashMap;
+
+use crate::{
+    ElmEnum, ElmEnumVariant, ElmField, ElmStruct, ElmType, EnumRepresentation, Identifier,
+    StructFields, VariantFields,
+};
+
+#[derive(Debug)]
+enum Shape {
+    Null,
+    Bool,
+    Int,
+    Float,
+    String,
+    /// `null` or the inner shape.
+    Maybe(Box<Shape>),
+    List(Box<Shape>),
+    /// Decoded with `Json.Decode.index`, which ignores additional elements.
+    Tuple(Vec<Shape>),
+    /// An object with these keys and values. Other keys are ignored when
+    /// decoding.
+    Object(Vec<(String, Shape)>),
+    /// Not enough is known about it to compare it with other shapes.
+    Unknown,
+}
+
+/// Structs nested deeper than this are `Unknown`, so recursive types end.
+const MAX_DEPTH: usize = 4;
+
+impl Shape {
+    fn of_type(ty: &ElmType, structs: &HashMap<Identifier, ElmStruct>, depth: usize) -> Shape {
+        match ty {
+            ElmType::Int => Shape::Int,
+            ElmType::Float => Shape::Float,
+            ElmType::String => Shape::String,
+            ElmType::Bool => Shape::Bool,
+            ElmType::Unit => Shape::Null,
+            ElmType::Maybe(inner) => Shape::Maybe(Box::new(Shape::of_type(inner, structs, depth))),
+            ElmType::List(inner) => Shape::List(Box::new(Shape::of_type(inner, structs, depth))),
+            ElmType::Tuple(elements) => Shape::Tuple(
+                elements
+                    .iter()
+                    .map(|element| Shape::of_type(element, structs, depth))
+                    .collect(),
+            ),
+            ElmType::NamedType(_, _) if depth >= MAX_DEPTH => Shape::Unknown,
+            ElmType::NamedType(name, _) => match structs.get(name).map(|s| &s.fields) {
+                Some(StructFields::Named(fields)) => Shape::of_fields(fields, structs, depth + 1),
+                Some(StructFields::Unit) => Shape::Null,
+                _ => Shape::Unknown,
+            },
+            ElmType::Array(_, _)
+            | ElmType::Result(_, _)
+            | ElmType::Dict(_, _)
+            | ElmType::TypeVariable(_) => Shape::Unknown,
+        }
+    }
+
+    fn of_fields(
+        fields: &[ElmField],
+        structs: &HashMap<Identifier, ElmStruct>,
+        depth: usize,
+    ) -> Shape {
+        // Flattened fields put unknown keys into the object.
+        if fields.iter().any(|field| field.flatten.is_some()) {
+            return Shape::Unknown;
+        }
+        Shape::Object(
+            fields
+                .iter()
+                .map(|field| {
+                    let shape = Shape::of_type(&field.ty, structs, depth);
+                    (field.serialized_name.clone(), shape)
+                })
+                .collect(),
+        )
+    }
+
+    fn of_variant(variant: &ElmEnumVariant, structs: &HashMap<Identifier, ElmStruct>) -> Shape {
+        match &variant.fields {
+            VariantFields::Unnamed(fields) if fields.is_empty() => Shape::Null,
+            VariantFields::Unnamed(fields) if fields.len() == 1 => {
+                Shape::of_type(&fields[0], structs, 0)
+            }
+            VariantFields::Unnamed(fields) => Shape::Tuple(
+                fields
+                    .iter()
+                    .map(|field| Shape::of_type(field, structs, 0))
+                    .collect(),
+            ),
+            VariantFields::Named(fields) => Shape::of_fields(fields, structs, 0),
+        }
+    }
+
+    /// Whether every value accepted by `other` is accepted by this shape.
+    fn accepts_all_of(&self, other: &Shape) -> bool {
+        match (self, other) {
+            (Shape::Unknown, _) | (_, Shape::Unknown) => false,
+            (Shape::Maybe(inner), Shape::Maybe(other)) => inner.accepts_all_of(other),
+            (Shape::Maybe(_), Shape::Null) => true,
+            (Shape::Maybe(inner), other) => inner.accepts_all_of(other),
+            (Shape::Null, Shape::Null)
+            | (Shape::Bool, Shape::Bool)
+            | (Shape::Int, Shape::Int)
+            | (Shape::Float, Shape::Int | Shape::Float)
+            | (Shape::String, Shape::String) => true,
+            (Shape::List(inner), Shape::List(other)) => inner.accepts_all_of(other),
+            (Shape::List(inner), Shape::Tuple(others)) => {
+                others.iter().all(|other| inner.accepts_all_of(other))
+            }
+            (Shape::Tuple(elements), Shape::Tuple(others)) => {
+                elements.len() <= others.len()
+                    && elements
+                        .iter()
+                        .zip(others)
+                        .all(|(element, other)| element.accepts_all_of(other))
+            }
+            // Every key we need has to be there, with a value we accept.
+            (Shape::Object(fields), Shape::Object(others)) => fields.iter().all(|(key, shape)| {
+                others
+                    .iter()
+                    .any(|(other_key, other)| key == other_key && shape.accepts_all_of(other))
+            }),
+            _ => false,
+        }
+    }
+}
+
+/// Warns about variants of an untagged enum that are shadowed by an earlier
+/// variant.
+pub fn untagged_warnings(
+    elm_enum: &ElmEnum,
+    structs: &HashMap<Identifier, ElmStruct>,
+) -> Vec<String> {
+    if elm_enum.representation != EnumRepresentation::Untagged {
+        return vec![];
+    }
+    let shapes: Vec<Shape> = elm_enum
+        .variants
+        .iter()
+        .map(|variant| Shape::of_variant(variant, structs))
+        .collect();
+    let mut warnings = vec![];
+    for (i, shape) in shapes.iter().enumerate() {
+        if let Some(earlier) = (0..i).find(|&j| shapes[j].accepts_all_of(shape)) {
+            warnings.push(format!(
+                "Variant {} of untagged enum {} can never be decoded, because variant {} before it accepts the same JSON",
+                elm_enum.variants[i].name.0, elm_enum.name.0, elm_enum.variants[earlier].name.0
+            ));
+        }
+    }
+    warnings
+}
@@ -0,0 +1,99 @@
+module AdjacentlyTagged exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type Frame
+    = Heartbeat
+    | Text String
+    | Cursor Int Int
+    | Resize { width : Int, height : Int }
+
+encodeFrame : Frame -> Json.Encode.Value
+encodeFrame frame =
+    case frame of
+        Heartbeat ->
+            Json.Encode.object
+                [ ( "t", Json.Encode.string "Heartbeat" )
+                ]
+
+        Text x ->
+            Json.Encode.object
+                [ ( "t", Json.Encode.string "Text" )
+                , ( "c", Json.Encode.string x )
+                ]
+
+        Cursor x0 x1 ->
+            Json.Encode.object
+                [ ( "t", Json.Encode.string "Cursor" )
+                , ( "c"
+                  , Json.Encode.list identity
+                        [ Json.Encode.int x0
+                        , Json.Encode.int x1
+                        ]
+                  )
+                ]
+
+        Resize x ->
+            Json.Encode.object
+                [ ( "t", Json.Encode.string "Resize" )
+                , ( "c"
+                  , Json.Encode.object
+                        [ ( "width", Json.Encode.int x.width )
+                        , ( "height", Json.Encode.int x.height )
+                        ]
+                  )
+                ]
+
+
+decodeFrame : Json.Decode.Decoder Frame
+decodeFrame =
+    Json.Decode.field "t" Json.Decode.string
+        |> Json.Decode.andThen
+            (\tag ->
+                case tag of
+                    "Heartbeat" ->
+                        decodeFrameHeartbeat
+
+                    "Text" ->
+                        decodeFrameText
+
+                    "Cursor" ->
+                        decodeFrameCursor
+
+                    "Resize" ->
+                        decodeFrameResize
+
+                    _ ->
+                        Json.Decode.fail ("Unknown variant " ++ tag)
+            )
+
+
+decodeFrameHeartbeat : Json.Decode.Decoder Frame
+decodeFrameHeartbeat =
+    Json.Decode.succeed Heartbeat
+
+
+decodeFrameText : Json.Decode.Decoder Frame
+decodeFrameText =
+    Json.Decode.field "c" (Json.Decode.map Text Json.Decode.string)
+
+
+decodeFrameCursor : Json.Decode.Decoder Frame
+decodeFrameCursor =
+    Json.Decode.field "c"
+        (Json.Decode.succeed Cursor
+            |> Json.Decode.Pipeline.custom (Json.Decode.index 0 Json.Decode.int)
+            |> Json.Decode.Pipeline.custom (Json.Decode.index 1 Json.Decode.int)
+        )
+
+
+decodeFrameResize : Json.Decode.Decoder Frame
+decodeFrameResize =
+    Json.Decode.field "c"
+        (Json.Decode.succeed (\width height -> Resize { width = width, height = height })
+            |> Json.Decode.Pipeline.required "width" Json.Decode.int
+            |> Json.Decode.Pipeline.required "height" Json.Decode.int
+        )
@@ -0,0 +1,196 @@
+module Aliases exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Signup =
+    { account : Account
+    , plan : Plan
+    , notices : (List Notice)
+    }
+
+encodeSignup : Signup -> Json.Encode.Value
+encodeSignup signup =
+    Json.Encode.object
+        [ ( "account", encodeAccount signup.account )
+        , ( "plan", encodePlan signup.plan )
+        , ( "notices", (Json.Encode.list encodeNotice) signup.notices )
+        ]
+
+decodeSignup : Json.Decode.Decoder Signup
+decodeSignup =
+    Json.Decode.succeed Signup
+        |> Json.Decode.Pipeline.required "account" decodeAccount
+        |> Json.Decode.Pipeline.required "plan" decodePlan
+        |> Json.Decode.Pipeline.required "notices" (Json.Decode.list decodeNotice)
+
+type alias Account =
+    { name : String
+    , email : String
+    }
+
+encodeAccount : Account -> Json.Encode.Value
+encodeAccount account =
+    Json.Encode.object
+        [ ( "name", Json.Encode.string account.name )
+        , ( "email", Json.Encode.string account.email )
+        ]
+
+decodeAccount : Json.Decode.Decoder Account
+decodeAccount =
+    Json.Decode.succeed Account
+        |> Json.Decode.Pipeline.custom (Json.Decode.oneOf [ Json.Decode.field "name" Json.Decode.string, Json.Decode.field "username" Json.Decode.string, Json.Decode.field "login" Json.Decode.string ])
+        |> Json.Decode.Pipeline.custom (Json.Decode.map (Maybe.withDefault "") (decodeFirstField [ "email", "mail" ] Json.Decode.string))
+
+type Plan
+    = Free
+    | Paid Int
+    | Unknown
+
+encodePlan : Plan -> Json.Encode.Value
+encodePlan plan =
+    case plan of
+        Free ->
+            Json.Encode.string "Free"
+
+        Paid x ->
+            Json.Encode.object
+                [ ( "Paid", Json.Encode.int x )
+                ]
+
+        Unknown ->
+            Json.Encode.string "Unknown"
+
+
+decodePlan : Json.Decode.Decoder Plan
+decodePlan =
+    Json.Decode.oneOf
+        [ decodePlanFree
+        , decodePlanPaid
+        , decodePlanUnknown
+        ]
+
+
+decodePlanFree : Json.Decode.Decoder Plan
+decodePlanFree =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "Free" ->
+                    Json.Decode.succeed Free
+
+                "Gratis" ->
+                    Json.Decode.succeed Free
+
+                _ ->
+                    Json.Decode.fail "Expected variant Free"
+        )
+        Json.Decode.string
+
+
+decodePlanPaid : Json.Decode.Decoder Plan
+decodePlanPaid =
+    Json.Decode.oneOf
+        [ Json.Decode.succeed Paid
+            |> Json.Decode.Pipeline.required "Paid" Json.Decode.int
+        , Json.Decode.succeed Paid
+            |> Json.Decode.Pipeline.required "Pro" Json.Decode.int
+        ]
+
+
+decodePlanUnknown : Json.Decode.Decoder Plan
+decodePlanUnknown =
+    Json.Decode.string
+        |> Json.Decode.andThen
+            (\str ->
+                if List.member str [ "Free", "Gratis", "Paid", "Pro" ] then
+                    Json.Decode.fail ("Unexpected variant " ++ str)
+
+                else
+                    Json.Decode.succeed Unknown
+            )
+
+type Notice
+    = Info { text : String }
+    | Warning { text : String }
+    | Unsupported
+
+encodeNotice : Notice -> Json.Encode.Value
+encodeNotice notice =
+    case notice of
+        Info x ->
+            Json.Encode.object
+                [ ( "type", Json.Encode.string "Info" )
+                , ( "text", Json.Encode.string x.text )
+                ]
+
+        Warning x ->
+            Json.Encode.object
+                [ ( "type", Json.Encode.string "Warning" )
+                , ( "text", Json.Encode.string x.text )
+                ]
+
+        Unsupported ->
+            Json.Encode.object
+                [ ( "type", Json.Encode.string "Unsupported" )
+                ]
+
+
+decodeNotice : Json.Decode.Decoder Notice
+decodeNotice =
+    Json.Decode.field "type" Json.Decode.string
+        |> Json.Decode.andThen
+            (\tag ->
+                case tag of
+                    "Info" ->
+                        decodeNoticeInfo
+
+                    "note" ->
+                        decodeNoticeInfo
+
+                    "Warning" ->
+                        decodeNoticeWarning
+
+                    "Unsupported" ->
+                        decodeNoticeUnsupported
+
+                    _ ->
+                        decodeNoticeUnsupported
+            )
+
+
+decodeNoticeInfo : Json.Decode.Decoder Notice
+decodeNoticeInfo =
+    Json.Decode.succeed (\text -> Info { text = text })
+        |> Json.Decode.Pipeline.required "text" Json.Decode.string
+
+
+decodeNoticeWarning : Json.Decode.Decoder Notice
+decodeNoticeWarning =
+    Json.Decode.succeed (\text -> Warning { text = text })
+        |> Json.Decode.Pipeline.required "text" Json.Decode.string
+
+
+decodeNoticeUnsupported : Json.Decode.Decoder Notice
+decodeNoticeUnsupported =
+    Json.Decode.succeed Unsupported
+
+decodeFirstField : List String -> Json.Decode.Decoder v -> Json.Decode.Decoder (Maybe v)
+decodeFirstField keys valueDecoder =
+    case keys of
+        [] ->
+            Json.Decode.succeed Nothing
+
+        key :: otherKeys ->
+            Json.Decode.maybe (Json.Decode.field key Json.Decode.value)
+                |> Json.Decode.andThen
+                    (\found ->
+                        case found of
+                            Just _ ->
+                                Json.Decode.map Just (Json.Decode.field key valueDecoder)
+
+                            Nothing ->
+                                decodeFirstField otherKeys valueDecoder
+                    )
@@ -0,0 +1,150 @@
+module CLikeEnums exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Pixel =
+    { color : Color
+    , level : Level
+    , size : Size
+    }
+
+encodePixel : Pixel -> Json.Encode.Value
+encodePixel pixel =
+    Json.Encode.object
+        [ ( "color", encodeColor pixel.color )
+        , ( "level", encodeLevel pixel.level )
+        , ( "size", encodeSize pixel.size )
+        ]
+
+decodePixel : Json.Decode.Decoder Pixel
+decodePixel =
+    Json.Decode.succeed Pixel
+        |> Json.Decode.Pipeline.required "color" decodeColor
+        |> Json.Decode.Pipeline.required "level" decodeLevel
+        |> Json.Decode.Pipeline.required "size" decodeSize
+
+type Color
+    = Red
+    | Green
+    | Blue
+
+encodeColor : Color -> Json.Encode.Value
+encodeColor color =
+    case color of
+        Red ->
+            Json.Encode.int 1
+
+        Green ->
+            Json.Encode.int 2
+
+        Blue ->
+            Json.Encode.int 3
+
+
+decodeColor : Json.Decode.Decoder Color
+decodeColor =
+    Json.Decode.int
+        |> Json.Decode.andThen
+            (\value ->
+                case value of
+                    1 ->
+                        Json.Decode.succeed Red
+
+                    2 ->
+                        Json.Decode.succeed Green
+
+                    3 ->
+                        Json.Decode.succeed Blue
+
+                    _ ->
+                        Json.Decode.fail ("Unknown value for Color: " ++ String.fromInt value)
+            )
+
+type Level
+    = Low
+    | Normal
+    | High
+
+encodeLevel : Level -> Json.Encode.Value
+encodeLevel level =
+    case level of
+        Low ->
+            Json.Encode.int (-1)
+
+        Normal ->
+            Json.Encode.int 0
+
+        High ->
+            Json.Encode.int 5
+
+
+decodeLevel : Json.Decode.Decoder Level
+decodeLevel =
+    Json.Decode.int
+        |> Json.Decode.andThen
+            (\value ->
+                case value of
+                    -1 ->
+                        Json.Decode.succeed Low
+
+                    0 ->
+                        Json.Decode.succeed Normal
+
+                    5 ->
+                        Json.Decode.succeed High
+
+                    _ ->
+                        Json.Decode.fail ("Unknown value for Level: " ++ String.fromInt value)
+            )
+
+type Size
+    = Small
+    | Large
+
+encodeSize : Size -> Json.Encode.Value
+encodeSize size =
+    case size of
+        Small ->
+            Json.Encode.string "Small"
+
+        Large ->
+            Json.Encode.string "Large"
+
+
+decodeSize : Json.Decode.Decoder Size
+decodeSize =
+    Json.Decode.oneOf
+        [ decodeSizeSmall
+        , decodeSizeLarge
+        ]
+
+
+decodeSizeSmall : Json.Decode.Decoder Size
+decodeSizeSmall =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "Small" ->
+                    Json.Decode.succeed Small
+
+                _ ->
+                    Json.Decode.fail "Expected variant Small"
+        )
+        Json.Decode.string
+
+
+decodeSizeLarge : Json.Decode.Decoder Size
+decodeSizeLarge =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "Large" ->
+                    Json.Decode.succeed Large
+
+                _ ->
+                    Json.Decode.fail "Expected variant Large"
+        )
+        Json.Decode.string
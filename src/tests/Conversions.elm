@@ -0,0 +1,89 @@
+module Conversions exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Reading =
+    { distance : Meters
+    , tags : Tags
+    , color : Color
+    , temperature : Temperature
+    }
+
+encodeReading : Reading -> Json.Encode.Value
+encodeReading reading =
+    Json.Encode.object
+        [ ( "distance", encodeMeters reading.distance )
+        , ( "tags", encodeTags reading.tags )
+        , ( "color", encodeColor reading.color )
+        , ( "temperature", encodeTemperature reading.temperature )
+        ]
+
+decodeReading : Json.Decode.Decoder Reading
+decodeReading =
+    Json.Decode.succeed Reading
+        |> Json.Decode.Pipeline.required "distance" decodeMeters
+        |> Json.Decode.Pipeline.required "tags" decodeTags
+        |> Json.Decode.Pipeline.required "color" decodeColor
+        |> Json.Decode.Pipeline.required "temperature" decodeTemperature
+
+type alias Meters =
+    Float
+
+encodeMeters : Meters -> Json.Encode.Value
+encodeMeters meters =
+    Json.Encode.float meters
+
+decodeMeters : Json.Decode.Decoder Meters
+decodeMeters =
+    Json.Decode.float
+
+type alias Tags =
+    (List String)
+
+encodeTags : Tags -> Json.Encode.Value
+encodeTags tags =
+    (Json.Encode.list Json.Encode.string) tags
+
+decodeTags : Json.Decode.Decoder Tags
+decodeTags =
+    (Json.Decode.list Json.Decode.string)
+
+type alias Color =
+    String
+
+encodeColor : Color -> Json.Encode.Value
+encodeColor color =
+    Json.Encode.string color
+
+decodeColor : Json.Decode.Decoder Color
+decodeColor =
+    Json.Decode.string
+
+type alias Temperature =
+    RawTemperature
+
+encodeTemperature : Temperature -> Json.Encode.Value
+encodeTemperature temperature =
+    encodeRawTemperature temperature
+
+decodeTemperature : Json.Decode.Decoder Temperature
+decodeTemperature =
+    decodeRawTemperature
+
+type alias RawTemperature =
+    { kelvin : Float
+    }
+
+encodeRawTemperature : RawTemperature -> Json.Encode.Value
+encodeRawTemperature rawtemperature =
+    Json.Encode.object
+        [ ( "kelvin", Json.Encode.float rawtemperature.kelvin )
+        ]
+
+decodeRawTemperature : Json.Decode.Decoder RawTemperature
+decodeRawTemperature =
+    Json.Decode.succeed RawTemperature
+        |> Json.Decode.Pipeline.required "kelvin" Json.Decode.float
@@ -0,0 +1,139 @@
+module Derive exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type SessionToken
+    = SessionToken String
+
+encodeSessionToken : SessionToken -> Json.Encode.Value
+encodeSessionToken (SessionToken x) =
+    Json.Encode.string x
+
+decodeSessionToken : Json.Decode.Decoder SessionToken
+decodeSessionToken =
+    Json.Decode.map SessionToken Json.Decode.string
+
+type alias Login =
+    { user : String
+    , token : SessionToken
+    }
+
+encodeLogin : Login -> Json.Encode.Value
+encodeLogin login =
+    Json.Encode.object
+        [ ( "user", Json.Encode.string login.user )
+        , ( "token", encodeSessionToken login.token )
+        ]
+
+decodeLogin : Json.Decode.Decoder Login
+decodeLogin =
+    Json.Decode.succeed Login
+        |> Json.Decode.Pipeline.required "user" Json.Decode.string
+        |> Json.Decode.Pipeline.required "token" decodeSessionToken
+
+type alias Paged t =
+    { items : (List t)
+    , total : Int
+    }
+
+encodePaged : (t -> Json.Encode.Value) -> Paged t -> Json.Encode.Value
+encodePaged encodeT paged =
+    Json.Encode.object
+        [ ( "items", (Json.Encode.list encodeT) paged.items )
+        , ( "total", Json.Encode.int paged.total )
+        ]
+
+decodePaged : Json.Decode.Decoder t -> Json.Decode.Decoder (Paged t)
+decodePaged decodeT =
+    Json.Decode.succeed Paged
+        |> Json.Decode.Pipeline.required "items" (Json.Decode.list decodeT)
+        |> Json.Decode.Pipeline.required "total" Json.Decode.int
+
+type Presence
+    = Online
+    | Away { since : Int }
+
+encodePresence : Presence -> Json.Encode.Value
+encodePresence presence =
+    case presence of
+        Online ->
+            Json.Encode.string "Online"
+
+        Away x ->
+            Json.Encode.object
+                [ ( "Away"
+                  , Json.Encode.object
+                        [ ( "since", Json.Encode.int x.since )
+                        ]
+                  )
+                ]
+
+
+decodePresence : Json.Decode.Decoder Presence
+decodePresence =
+    Json.Decode.oneOf
+        [ decodePresenceOnline
+        , decodePresenceAway
+        ]
+
+
+decodePresenceOnline : Json.Decode.Decoder Presence
+decodePresenceOnline =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "Online" ->
+                    Json.Decode.succeed Online
+
+                _ ->
+                    Json.Decode.fail "Expected variant Online"
+        )
+        Json.Decode.string
+
+
+decodePresenceAway : Json.Decode.Decoder Presence
+decodePresenceAway =
+    Json.Decode.field "Away"
+        (Json.Decode.succeed (\since -> Away { since = since })
+            |> Json.Decode.Pipeline.required "since" Json.Decode.int
+        )
+
+type Reply
+    = Text String
+    | Thread (List Reply)
+
+encodeReply : Reply -> Json.Encode.Value
+encodeReply reply =
+    case reply of
+        Text x ->
+            Json.Encode.object
+                [ ( "Text", Json.Encode.string x )
+                ]
+
+        Thread x ->
+            Json.Encode.object
+                [ ( "Thread", (Json.Encode.list encodeReply) x )
+                ]
+
+
+decodeReply : Json.Decode.Decoder Reply
+decodeReply =
+    Json.Decode.oneOf
+        [ decodeReplyText
+        , decodeReplyThread
+        ]
+
+
+decodeReplyText : Json.Decode.Decoder Reply
+decodeReplyText =
+    Json.Decode.succeed Text
+        |> Json.Decode.Pipeline.required "Text" Json.Decode.string
+
+
+decodeReplyThread : Json.Decode.Decoder Reply
+decodeReplyThread =
+    Json.Decode.succeed Thread
+        |> Json.Decode.Pipeline.required "Thread" (Json.Decode.list decodeReply)
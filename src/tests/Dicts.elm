@@ -0,0 +1,28 @@
+module Dicts exposing (..)
+
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Inventory =
+    { stock : (Dict String Int)
+    , prices : (Dict String Float)
+    , tags : (Dict String (List String))
+    }
+
+encodeInventory : Inventory -> Json.Encode.Value
+encodeInventory inventory =
+    Json.Encode.object
+        [ ( "stock", (Json.Encode.dict identity Json.Encode.int) inventory.stock )
+        , ( "prices", (Json.Encode.dict identity Json.Encode.float) inventory.prices )
+        , ( "tags", (Json.Encode.dict identity (Json.Encode.list Json.Encode.string)) inventory.tags )
+        ]
+
+decodeInventory : Json.Decode.Decoder Inventory
+decodeInventory =
+    Json.Decode.succeed Inventory
+        |> Json.Decode.Pipeline.required "stock" (Json.Decode.dict Json.Decode.int)
+        |> Json.Decode.Pipeline.required "prices" (Json.Decode.dict Json.Decode.float)
+        |> Json.Decode.Pipeline.required "tags" (Json.Decode.dict (Json.Decode.list Json.Decode.string))
@@ -0,0 +1,106 @@
+module Events exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Log =
+    { events : (List Event)
+    }
+
+encodeLog : Log -> Json.Encode.Value
+encodeLog log =
+    Json.Encode.object
+        [ ( "events", (Json.Encode.list encodeEvent) log.events )
+        ]
+
+decodeLog : Json.Decode.Decoder Log
+decodeLog =
+    Json.Decode.succeed Log
+        |> Json.Decode.Pipeline.required "events" (Json.Decode.list decodeEvent)
+
+type Event
+    = Started
+    | Moved { x : Int, y : Int }
+    | Renamed { oldName : String, newName : (Maybe String) }
+    | Scaled Float
+
+encodeEvent : Event -> Json.Encode.Value
+encodeEvent event =
+    case event of
+        Started ->
+            Json.Encode.string "Started"
+
+        Moved x ->
+            Json.Encode.object
+                [ ( "Moved"
+                  , Json.Encode.object
+                        [ ( "x", Json.Encode.int x.x )
+                        , ( "y", Json.Encode.int x.y )
+                        ]
+                  )
+                ]
+
+        Renamed x ->
+            Json.Encode.object
+                [ ( "Renamed"
+                  , Json.Encode.object
+                        [ ( "old_name", Json.Encode.string x.oldName )
+                        , ( "new_name", (Maybe.map Json.Encode.string >> Maybe.withDefault Json.Encode.null) x.newName )
+                        ]
+                  )
+                ]
+
+        Scaled x ->
+            Json.Encode.object
+                [ ( "Scaled", Json.Encode.float x )
+                ]
+
+
+decodeEvent : Json.Decode.Decoder Event
+decodeEvent =
+    Json.Decode.oneOf
+        [ decodeEventStarted
+        , decodeEventMoved
+        , decodeEventRenamed
+        , decodeEventScaled
+        ]
+
+
+decodeEventStarted : Json.Decode.Decoder Event
+decodeEventStarted =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "Started" ->
+                    Json.Decode.succeed Started
+
+                _ ->
+                    Json.Decode.fail "Expected variant Started"
+        )
+        Json.Decode.string
+
+
+decodeEventMoved : Json.Decode.Decoder Event
+decodeEventMoved =
+    Json.Decode.field "Moved"
+        (Json.Decode.succeed (\x y -> Moved { x = x, y = y })
+            |> Json.Decode.Pipeline.required "x" Json.Decode.int
+            |> Json.Decode.Pipeline.required "y" Json.Decode.int
+        )
+
+
+decodeEventRenamed : Json.Decode.Decoder Event
+decodeEventRenamed =
+    Json.Decode.field "Renamed"
+        (Json.Decode.succeed (\oldName newName -> Renamed { oldName = oldName, newName = newName })
+            |> Json.Decode.Pipeline.required "old_name" Json.Decode.string
+            |> Json.Decode.Pipeline.required "new_name" (Json.Decode.nullable Json.Decode.string)
+        )
+
+
+decodeEventScaled : Json.Decode.Decoder Event
+decodeEventScaled =
+    Json.Decode.succeed Scaled
+        |> Json.Decode.Pipeline.required "Scaled" Json.Decode.float
@@ -0,0 +1,166 @@
+module Flatten exposing (..)
+
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias History =
+    { document : Document
+    , changes : (List Change)
+    }
+
+encodeHistory : History -> Json.Encode.Value
+encodeHistory history =
+    Json.Encode.object
+        [ ( "document", encodeDocument history.document )
+        , ( "changes", (Json.Encode.list encodeChange) history.changes )
+        ]
+
+decodeHistory : Json.Decode.Decoder History
+decodeHistory =
+    Json.Decode.succeed History
+        |> Json.Decode.Pipeline.required "document" decodeDocument
+        |> Json.Decode.Pipeline.required "changes" (Json.Decode.list decodeChange)
+
+type alias Document =
+    { id : Int
+    , audit : Audit
+    , extra : (Dict String String)
+    }
+
+encodeDocument : Document -> Json.Encode.Value
+encodeDocument document =
+    Json.Encode.object
+        (List.filterMap identity
+            [ Just ( "id", Json.Encode.int document.id )
+            ]
+            ++ objectPairs (encodeAudit document.audit)
+            ++ objectPairs ((Json.Encode.dict identity Json.Encode.string) document.extra)
+        )
+
+decodeDocument : Json.Decode.Decoder Document
+decodeDocument =
+    Json.Decode.succeed Document
+        |> Json.Decode.Pipeline.required "id" Json.Decode.int
+        |> Json.Decode.Pipeline.custom decodeAudit
+        |> Json.Decode.Pipeline.custom (decodeRemainingFields [ "id", "created_by", "version", "reviewed" ] Json.Decode.string)
+
+type alias Audit =
+    { meta : Meta
+    , reviewed : Bool
+    }
+
+encodeAudit : Audit -> Json.Encode.Value
+encodeAudit audit =
+    Json.Encode.object
+        (List.filterMap identity
+            [ Just ( "reviewed", Json.Encode.bool audit.reviewed )
+            ]
+            ++ objectPairs (encodeMeta audit.meta)
+        )
+
+decodeAudit : Json.Decode.Decoder Audit
+decodeAudit =
+    Json.Decode.succeed Audit
+        |> Json.Decode.Pipeline.custom decodeMeta
+        |> Json.Decode.Pipeline.required "reviewed" Json.Decode.bool
+
+type alias Meta =
+    { createdBy : String
+    , version : Int
+    }
+
+encodeMeta : Meta -> Json.Encode.Value
+encodeMeta meta =
+    Json.Encode.object
+        [ ( "created_by", Json.Encode.string meta.createdBy )
+        , ( "version", Json.Encode.int meta.version )
+        ]
+
+decodeMeta : Json.Decode.Decoder Meta
+decodeMeta =
+    Json.Decode.succeed Meta
+        |> Json.Decode.Pipeline.required "created_by" Json.Decode.string
+        |> Json.Decode.Pipeline.required "version" Json.Decode.int
+
+type Change
+    = Created { meta : Meta }
+    | Tagged { tag : String, labels : (Dict String Int) }
+
+encodeChange : Change -> Json.Encode.Value
+encodeChange change =
+    case change of
+        Created x ->
+            Json.Encode.object
+                (List.filterMap identity
+                    [ Just ( "kind", Json.Encode.string "Created" )
+                    ]
+                    ++ objectPairs (encodeMeta x.meta)
+                )
+
+        Tagged x ->
+            Json.Encode.object
+                (List.filterMap identity
+                    [ Just ( "kind", Json.Encode.string "Tagged" )
+                    , Just ( "tag", Json.Encode.string x.tag )
+                    ]
+                    ++ objectPairs ((Json.Encode.dict identity Json.Encode.int) x.labels)
+                )
+
+
+decodeChange : Json.Decode.Decoder Change
+decodeChange =
+    Json.Decode.field "kind" Json.Decode.string
+        |> Json.Decode.andThen
+            (\tag ->
+                case tag of
+                    "Created" ->
+                        decodeChangeCreated
+
+                    "Tagged" ->
+                        decodeChangeTagged
+
+                    _ ->
+                        Json.Decode.fail ("Unknown variant " ++ tag)
+            )
+
+
+decodeChangeCreated : Json.Decode.Decoder Change
+decodeChangeCreated =
+    Json.Decode.succeed (\meta -> Created { meta = meta })
+        |> Json.Decode.Pipeline.custom decodeMeta
+
+
+decodeChangeTagged : Json.Decode.Decoder Change
+decodeChangeTagged =
+    Json.Decode.succeed (\tag labels -> Tagged { tag = tag, labels = labels })
+        |> Json.Decode.Pipeline.required "tag" Json.Decode.string
+        |> Json.Decode.Pipeline.custom (decodeRemainingFields [ "kind", "tag" ] Json.Decode.int)
+
+objectPairs : Json.Encode.Value -> List ( String, Json.Encode.Value )
+objectPairs value =
+    Json.Decode.decodeValue (Json.Decode.keyValuePairs Json.Decode.value) value
+        |> Result.withDefault []
+
+decodeRemainingFields : List String -> Json.Decode.Decoder v -> Json.Decode.Decoder (Dict String v)
+decodeRemainingFields knownKeys valueDecoder =
+    let
+        decodeEntry ( key, value ) =
+            case Json.Decode.decodeValue valueDecoder value of
+                Ok decoded ->
+                    Json.Decode.succeed ( key, decoded )
+
+                Err error ->
+                    Json.Decode.fail (Json.Decode.errorToString error)
+    in
+    Json.Decode.keyValuePairs Json.Decode.value
+        |> Json.Decode.andThen
+            (\pairs ->
+                pairs
+                    |> List.filter (\( key, _ ) -> not (List.member key knownKeys))
+                    |> List.map decodeEntry
+                    |> List.foldr (Json.Decode.map2 (::)) (Json.Decode.succeed [])
+                    |> Json.Decode.map Dict.fromList
+            )
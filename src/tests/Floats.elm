@@ -0,0 +1,27 @@
+module Floats exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Measurement =
+    { value : Float
+    , error : Float
+    , calibration : (Maybe Float)
+    }
+
+encodeMeasurement : Measurement -> Json.Encode.Value
+encodeMeasurement measurement =
+    Json.Encode.object
+        [ ( "value", Json.Encode.float measurement.value )
+        , ( "error", Json.Encode.float measurement.error )
+        , ( "calibration", (Maybe.map Json.Encode.float >> Maybe.withDefault Json.Encode.null) measurement.calibration )
+        ]
+
+decodeMeasurement : Json.Decode.Decoder Measurement
+decodeMeasurement =
+    Json.Decode.succeed Measurement
+        |> Json.Decode.Pipeline.required "value" Json.Decode.float
+        |> Json.Decode.Pipeline.required "error" Json.Decode.float
+        |> Json.Decode.Pipeline.required "calibration" (Json.Decode.nullable Json.Decode.float)
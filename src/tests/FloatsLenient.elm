@@ -0,0 +1,27 @@
+module FloatsLenient exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Measurement =
+    { value : (Maybe Float)
+    , error : (Maybe Float)
+    , calibration : (Maybe Float)
+    }
+
+encodeMeasurement : Measurement -> Json.Encode.Value
+encodeMeasurement measurement =
+    Json.Encode.object
+        [ ( "value", (Maybe.map Json.Encode.float >> Maybe.withDefault Json.Encode.null) measurement.value )
+        , ( "error", (Maybe.map Json.Encode.float >> Maybe.withDefault Json.Encode.null) measurement.error )
+        , ( "calibration", (Maybe.map Json.Encode.float >> Maybe.withDefault Json.Encode.null) measurement.calibration )
+        ]
+
+decodeMeasurement : Json.Decode.Decoder Measurement
+decodeMeasurement =
+    Json.Decode.succeed Measurement
+        |> Json.Decode.Pipeline.required "value" (Json.Decode.nullable Json.Decode.float)
+        |> Json.Decode.Pipeline.required "error" (Json.Decode.nullable Json.Decode.float)
+        |> Json.Decode.Pipeline.required "calibration" (Json.Decode.nullable Json.Decode.float)
@@ -0,0 +1,36 @@
+module FromOnly exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Forecast =
+    { high : Celsius
+    }
+
+encodeForecast : Forecast -> Json.Encode.Value
+encodeForecast forecast =
+    Json.Encode.object
+        [ ( "high", encodeCelsius forecast.high )
+        ]
+
+decodeForecast : Json.Decode.Decoder Forecast
+decodeForecast =
+    Json.Decode.succeed Forecast
+        |> Json.Decode.Pipeline.required "high" decodeCelsius
+
+type alias Celsius =
+    { degrees : Float
+    }
+
+encodeCelsius : Celsius -> Json.Encode.Value
+encodeCelsius celsius =
+    Json.Encode.object
+        [ ( "degrees", Json.Encode.float celsius.degrees )
+        ]
+
+decodeCelsius : Json.Decode.Decoder Celsius
+decodeCelsius =
+    Json.Decode.succeed Celsius
+        |> Json.Decode.Pipeline.required "degrees" Json.Decode.float
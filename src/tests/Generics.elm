@@ -0,0 +1,175 @@
+module Generics exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Deck =
+    { page : (Page Card)
+    , history : (List (Update Card))
+    , counts : (Labelled String (Maybe Int))
+    }
+
+encodeDeck : Deck -> Json.Encode.Value
+encodeDeck deck =
+    Json.Encode.object
+        [ ( "page", (encodePage encodeCard) deck.page )
+        , ( "history", (Json.Encode.list (encodeUpdate encodeCard)) deck.history )
+        , ( "counts", (encodeLabelled Json.Encode.string (Maybe.map Json.Encode.int >> Maybe.withDefault Json.Encode.null)) deck.counts )
+        ]
+
+decodeDeck : Json.Decode.Decoder Deck
+decodeDeck =
+    Json.Decode.succeed Deck
+        |> Json.Decode.Pipeline.required "page" (decodePage decodeCard)
+        |> Json.Decode.Pipeline.required "history" (Json.Decode.list (decodeUpdate decodeCard))
+        |> Json.Decode.Pipeline.required "counts" (decodeLabelled Json.Decode.string (Json.Decode.nullable Json.Decode.int))
+
+type alias Page t =
+    { items : (List t)
+    , total : Int
+    }
+
+encodePage : (t -> Json.Encode.Value) -> Page t -> Json.Encode.Value
+encodePage encodeT page =
+    Json.Encode.object
+        [ ( "items", (Json.Encode.list encodeT) page.items )
+        , ( "total", Json.Encode.int page.total )
+        ]
+
+decodePage : Json.Decode.Decoder t -> Json.Decode.Decoder (Page t)
+decodePage decodeT =
+    Json.Decode.succeed Page
+        |> Json.Decode.Pipeline.required "items" (Json.Decode.list decodeT)
+        |> Json.Decode.Pipeline.required "total" Json.Decode.int
+
+type alias Card =
+    { suit : String
+    , value : Int
+    }
+
+encodeCard : Card -> Json.Encode.Value
+encodeCard card =
+    Json.Encode.object
+        [ ( "suit", Json.Encode.string card.suit )
+        , ( "value", Json.Encode.int card.value )
+        ]
+
+decodeCard : Json.Decode.Decoder Card
+decodeCard =
+    Json.Decode.succeed Card
+        |> Json.Decode.Pipeline.required "suit" Json.Decode.string
+        |> Json.Decode.Pipeline.required "value" Json.Decode.int
+
+type alias Labelled k v =
+    { label : String
+    , entries : (List ( k, v ))
+    }
+
+encodeLabelled : (k -> Json.Encode.Value) -> (v -> Json.Encode.Value) -> Labelled k v -> Json.Encode.Value
+encodeLabelled encodeK encodeV labelled =
+    Json.Encode.object
+        [ ( "label", Json.Encode.string labelled.label )
+        , ( "entries", (encodeKeyValueList (keyToStringWith encodeK) encodeV) labelled.entries )
+        ]
+
+decodeLabelled : Json.Decode.Decoder k -> Json.Decode.Decoder v -> Json.Decode.Decoder (Labelled k v)
+decodeLabelled decodeK decodeV =
+    Json.Decode.succeed Labelled
+        |> Json.Decode.Pipeline.required "label" Json.Decode.string
+        |> Json.Decode.Pipeline.required "entries" (decodeKeyValueList (keyFromStringWith decodeK) decodeV)
+
+type Update t
+    = Replace t
+    | Clear
+
+encodeUpdate : (t -> Json.Encode.Value) -> Update t -> Json.Encode.Value
+encodeUpdate encodeT update =
+    case update of
+        Replace x ->
+            Json.Encode.object
+                [ ( "Replace", encodeT x )
+                ]
+
+        Clear ->
+            Json.Encode.string "Clear"
+
+
+decodeUpdate : Json.Decode.Decoder t -> Json.Decode.Decoder (Update t)
+decodeUpdate decodeT =
+    Json.Decode.oneOf
+        [ decodeUpdateReplace decodeT
+        , decodeUpdateClear decodeT
+        ]
+
+
+decodeUpdateReplace : Json.Decode.Decoder t -> Json.Decode.Decoder (Update t)
+decodeUpdateReplace decodeT =
+    Json.Decode.succeed Replace
+        |> Json.Decode.Pipeline.required "Replace" decodeT
+
+
+decodeUpdateClear : Json.Decode.Decoder t -> Json.Decode.Decoder (Update t)
+decodeUpdateClear decodeT =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "Clear" ->
+                    Json.Decode.succeed Clear
+
+                _ ->
+                    Json.Decode.fail "Expected variant Clear"
+        )
+        Json.Decode.string
+
+-- Elm can only use comparable types like Int and String as Dict keys.
+-- Maps with other keys, like enums, are represented as List ( k, v ).
+decodeKeyValueList : (String -> Maybe k) -> Json.Decode.Decoder v -> Json.Decode.Decoder (List ( k, v ))
+decodeKeyValueList keyFromString valueDecoder =
+    let
+        parseEntry ( key, value ) =
+            Maybe.map (\k -> ( k, value )) (keyFromString key)
+    in
+    Json.Decode.keyValuePairs valueDecoder
+        |> Json.Decode.andThen
+            (\pairs ->
+                let
+                    entries =
+                        List.filterMap parseEntry pairs
+                in
+                if List.length entries == List.length pairs then
+                    Json.Decode.succeed entries
+
+                else
+                    Json.Decode.fail "Unexpected map key"
+            )
+
+encodeKeyValueList : (k -> String) -> (v -> Json.Encode.Value) -> List ( k, v ) -> Json.Encode.Value
+encodeKeyValueList keyToString valueEncoder pairs =
+    Json.Encode.object (List.map (\( k, v ) -> ( keyToString k, valueEncoder v )) pairs)
+
+-- Numbers and bools are written as their JSON text, like "5" or "true".
+keyFromStringWith : Json.Decode.Decoder k -> String -> Maybe k
+keyFromStringWith keyDecoder key =
+    case Json.Decode.decodeValue keyDecoder (Json.Encode.string key) of
+        Ok decoded ->
+            Just decoded
+
+        Err _ ->
+            Json.Decode.decodeString keyDecoder key
+                |> Result.toMaybe
+
+-- serde_json writes number and bool keys as their JSON text.
+keyToStringWith : (k -> Json.Encode.Value) -> k -> String
+keyToStringWith keyEncoder key =
+    let
+        value =
+            keyEncoder key
+    in
+    case Json.Decode.decodeValue Json.Decode.string value of
+        Ok string ->
+            string
+
+        Err _ ->
+            Json.Encode.encode 0 value
@@ -0,0 +1,112 @@
+module IntegerEnums exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Meeting =
+    { day : Weekday
+    , offset : Offset
+    }
+
+encodeMeeting : Meeting -> Json.Encode.Value
+encodeMeeting meeting =
+    Json.Encode.object
+        [ ( "day", encodeWeekday meeting.day )
+        , ( "offset", encodeOffset meeting.offset )
+        ]
+
+decodeMeeting : Json.Decode.Decoder Meeting
+decodeMeeting =
+    Json.Decode.succeed Meeting
+        |> Json.Decode.Pipeline.required "day" decodeWeekday
+        |> Json.Decode.Pipeline.required "offset" decodeOffset
+
+type Weekday
+    = Monday
+    | Tuesday
+    | Wednesday
+    | Thursday
+    | Friday
+
+encodeWeekday : Weekday -> Json.Encode.Value
+encodeWeekday weekday =
+    case weekday of
+        Monday ->
+            Json.Encode.int 1
+
+        Tuesday ->
+            Json.Encode.int 2
+
+        Wednesday ->
+            Json.Encode.int 3
+
+        Thursday ->
+            Json.Encode.int 4
+
+        Friday ->
+            Json.Encode.int 5
+
+
+decodeWeekday : Json.Decode.Decoder Weekday
+decodeWeekday =
+    Json.Decode.int
+        |> Json.Decode.andThen
+            (\value ->
+                case value of
+                    1 ->
+                        Json.Decode.succeed Monday
+
+                    2 ->
+                        Json.Decode.succeed Tuesday
+
+                    3 ->
+                        Json.Decode.succeed Wednesday
+
+                    4 ->
+                        Json.Decode.succeed Thursday
+
+                    5 ->
+                        Json.Decode.succeed Friday
+
+                    _ ->
+                        Json.Decode.fail ("Unknown value for Weekday: " ++ String.fromInt value)
+            )
+
+type Offset
+    = Behind
+    | OnTime
+    | Ahead
+
+encodeOffset : Offset -> Json.Encode.Value
+encodeOffset offset =
+    case offset of
+        Behind ->
+            Json.Encode.int (-60)
+
+        OnTime ->
+            Json.Encode.int 0
+
+        Ahead ->
+            Json.Encode.int 60
+
+
+decodeOffset : Json.Decode.Decoder Offset
+decodeOffset =
+    Json.Decode.int
+        |> Json.Decode.andThen
+            (\value ->
+                case value of
+                    -60 ->
+                        Json.Decode.succeed Behind
+
+                    0 ->
+                        Json.Decode.succeed OnTime
+
+                    60 ->
+                        Json.Decode.succeed Ahead
+
+                    _ ->
+                        Json.Decode.fail ("Unknown value for Offset: " ++ String.fromInt value)
+            )
@@ -0,0 +1,90 @@
+module InternallyTagged exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Chat =
+    { room : String
+    , text : String
+    }
+
+encodeChat : Chat -> Json.Encode.Value
+encodeChat chat =
+    Json.Encode.object
+        [ ( "room", Json.Encode.string chat.room )
+        , ( "text", Json.Encode.string chat.text )
+        ]
+
+decodeChat : Json.Decode.Decoder Chat
+decodeChat =
+    Json.Decode.succeed Chat
+        |> Json.Decode.Pipeline.required "room" Json.Decode.string
+        |> Json.Decode.Pipeline.required "text" Json.Decode.string
+
+type ServerMessage
+    = Ping
+    | Joined { userName : String, at : Int }
+    | Said Chat
+
+encodeServerMessage : ServerMessage -> Json.Encode.Value
+encodeServerMessage servermessage =
+    case servermessage of
+        Ping ->
+            Json.Encode.object
+                [ ( "type", Json.Encode.string "ping" )
+                ]
+
+        Joined x ->
+            Json.Encode.object
+                [ ( "type", Json.Encode.string "joined" )
+                , ( "user_name", Json.Encode.string x.userName )
+                , ( "at", Json.Encode.int x.at )
+                ]
+
+        Said x ->
+            Json.Encode.object
+                (( "type", Json.Encode.string "said" ) :: objectPairs (encodeChat x))
+
+
+decodeServerMessage : Json.Decode.Decoder ServerMessage
+decodeServerMessage =
+    Json.Decode.field "type" Json.Decode.string
+        |> Json.Decode.andThen
+            (\tag ->
+                case tag of
+                    "ping" ->
+                        decodeServerMessagePing
+
+                    "joined" ->
+                        decodeServerMessageJoined
+
+                    "said" ->
+                        decodeServerMessageSaid
+
+                    _ ->
+                        Json.Decode.fail ("Unknown variant " ++ tag)
+            )
+
+
+decodeServerMessagePing : Json.Decode.Decoder ServerMessage
+decodeServerMessagePing =
+    Json.Decode.succeed Ping
+
+
+decodeServerMessageJoined : Json.Decode.Decoder ServerMessage
+decodeServerMessageJoined =
+    Json.Decode.succeed (\userName at -> Joined { userName = userName, at = at })
+        |> Json.Decode.Pipeline.required "user_name" Json.Decode.string
+        |> Json.Decode.Pipeline.required "at" Json.Decode.int
+
+
+decodeServerMessageSaid : Json.Decode.Decoder ServerMessage
+decodeServerMessageSaid =
+    Json.Decode.map Said decodeChat
+
+objectPairs : Json.Encode.Value -> List ( String, Json.Encode.Value )
+objectPairs value =
+    Json.Decode.decodeValue (Json.Decode.keyValuePairs Json.Decode.value) value
+        |> Result.withDefault []
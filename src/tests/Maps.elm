@@ -0,0 +1,169 @@
+module Maps exposing (..)
+
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Palette =
+    { names : (Dict Int String)
+    , weights : (Dict Int Float)
+    , usage : (List ( Color, Int ))
+    , enabled : (List ( Bool, String ))
+    , owners : (Dict Int String)
+    }
+
+encodePalette : Palette -> Json.Encode.Value
+encodePalette palette =
+    Json.Encode.object
+        [ ( "names", (Json.Encode.dict String.fromInt Json.Encode.string) palette.names )
+        , ( "weights", (Json.Encode.dict String.fromInt Json.Encode.float) palette.weights )
+        , ( "usage", (encodeKeyValueList (keyToStringWith encodeColor) Json.Encode.int) palette.usage )
+        , ( "enabled", (encodeKeyValueList boolKeyToString Json.Encode.string) palette.enabled )
+        , ( "owners", (Json.Encode.dict String.fromInt Json.Encode.string) palette.owners )
+        ]
+
+decodePalette : Json.Decode.Decoder Palette
+decodePalette =
+    Json.Decode.succeed Palette
+        |> Json.Decode.Pipeline.required "names" (decodeDictWithKeys String.toInt Json.Decode.string)
+        |> Json.Decode.Pipeline.required "weights" (decodeDictWithKeys String.toInt Json.Decode.float)
+        |> Json.Decode.Pipeline.required "usage" (decodeKeyValueList (keyFromStringWith decodeColor) Json.Decode.int)
+        |> Json.Decode.Pipeline.required "enabled" (decodeKeyValueList boolKeyFromString Json.Decode.string)
+        |> Json.Decode.Pipeline.required "owners" (decodeDictWithKeys String.toInt Json.Decode.string)
+
+type alias UserId =
+    Int
+
+encodeUserId : UserId -> Json.Encode.Value
+encodeUserId userid =
+    Json.Encode.int userid
+
+decodeUserId : Json.Decode.Decoder UserId
+decodeUserId =
+    Json.Decode.int
+
+type Color
+    = Red
+    | Green
+
+encodeColor : Color -> Json.Encode.Value
+encodeColor color =
+    case color of
+        Red ->
+            Json.Encode.string "Red"
+
+        Green ->
+            Json.Encode.string "Green"
+
+
+decodeColor : Json.Decode.Decoder Color
+decodeColor =
+    Json.Decode.oneOf
+        [ decodeColorRed
+        , decodeColorGreen
+        ]
+
+
+decodeColorRed : Json.Decode.Decoder Color
+decodeColorRed =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "Red" ->
+                    Json.Decode.succeed Red
+
+                _ ->
+                    Json.Decode.fail "Expected variant Red"
+        )
+        Json.Decode.string
+
+
+decodeColorGreen : Json.Decode.Decoder Color
+decodeColorGreen =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "Green" ->
+                    Json.Decode.succeed Green
+
+                _ ->
+                    Json.Decode.fail "Expected variant Green"
+        )
+        Json.Decode.string
+
+decodeDictWithKeys : (String -> Maybe comparable) -> Json.Decode.Decoder v -> Json.Decode.Decoder (Dict comparable v)
+decodeDictWithKeys keyFromString valueDecoder =
+    Json.Decode.map Dict.fromList (decodeKeyValueList keyFromString valueDecoder)
+
+-- Elm can only use comparable types like Int and String as Dict keys.
+-- Maps with other keys, like enums, are represented as List ( k, v ).
+decodeKeyValueList : (String -> Maybe k) -> Json.Decode.Decoder v -> Json.Decode.Decoder (List ( k, v ))
+decodeKeyValueList keyFromString valueDecoder =
+    let
+        parseEntry ( key, value ) =
+            Maybe.map (\k -> ( k, value )) (keyFromString key)
+    in
+    Json.Decode.keyValuePairs valueDecoder
+        |> Json.Decode.andThen
+            (\pairs ->
+                let
+                    entries =
+                        List.filterMap parseEntry pairs
+                in
+                if List.length entries == List.length pairs then
+                    Json.Decode.succeed entries
+
+                else
+                    Json.Decode.fail "Unexpected map key"
+            )
+
+encodeKeyValueList : (k -> String) -> (v -> Json.Encode.Value) -> List ( k, v ) -> Json.Encode.Value
+encodeKeyValueList keyToString valueEncoder pairs =
+    Json.Encode.object (List.map (\( k, v ) -> ( keyToString k, valueEncoder v )) pairs)
+
+-- Numbers and bools are written as their JSON text, like "5" or "true".
+keyFromStringWith : Json.Decode.Decoder k -> String -> Maybe k
+keyFromStringWith keyDecoder key =
+    case Json.Decode.decodeValue keyDecoder (Json.Encode.string key) of
+        Ok decoded ->
+            Just decoded
+
+        Err _ ->
+            Json.Decode.decodeString keyDecoder key
+                |> Result.toMaybe
+
+-- serde_json writes number and bool keys as their JSON text.
+keyToStringWith : (k -> Json.Encode.Value) -> k -> String
+keyToStringWith keyEncoder key =
+    let
+        value =
+            keyEncoder key
+    in
+    case Json.Decode.decodeValue Json.Decode.string value of
+        Ok string ->
+            string
+
+        Err _ ->
+            Json.Encode.encode 0 value
+
+boolKeyFromString : String -> Maybe Bool
+boolKeyFromString key =
+    case key of
+        "true" ->
+            Just True
+
+        "false" ->
+            Just False
+
+        _ ->
+            Nothing
+
+boolKeyToString : Bool -> String
+boolKeyToString key =
+    if key then
+        "true"
+
+    else
+        "false"
@@ -0,0 +1,134 @@
+module ModulePaths exposing (..)
+
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Table =
+    { players : (List Player)
+    , hand : (List GameCard)
+    , offers : (List ShopCard)
+    , scores : (Dict String Int)
+    }
+
+encodeTable : Table -> Json.Encode.Value
+encodeTable table =
+    Json.Encode.object
+        [ ( "players", (Json.Encode.list encodePlayer) table.players )
+        , ( "hand", (Json.Encode.list encodeGameCard) table.hand )
+        , ( "offers", (Json.Encode.list encodeShopCard) table.offers )
+        , ( "scores", (Json.Encode.dict identity Json.Encode.int) table.scores )
+        ]
+
+decodeTable : Json.Decode.Decoder Table
+decodeTable =
+    Json.Decode.succeed Table
+        |> Json.Decode.Pipeline.required "players" (Json.Decode.list decodePlayer)
+        |> Json.Decode.Pipeline.required "hand" (Json.Decode.list decodeGameCard)
+        |> Json.Decode.Pipeline.required "offers" (Json.Decode.list decodeShopCard)
+        |> Json.Decode.Pipeline.required "scores" (Json.Decode.dict Json.Decode.int)
+
+type alias Player =
+    { name : String
+    , favorite : (Maybe ShopCard)
+    }
+
+encodePlayer : Player -> Json.Encode.Value
+encodePlayer player =
+    Json.Encode.object
+        [ ( "name", Json.Encode.string player.name )
+        , ( "favorite", (Maybe.map encodeShopCard >> Maybe.withDefault Json.Encode.null) player.favorite )
+        ]
+
+decodePlayer : Json.Decode.Decoder Player
+decodePlayer =
+    Json.Decode.succeed Player
+        |> Json.Decode.Pipeline.required "name" Json.Decode.string
+        |> Json.Decode.Pipeline.required "favorite" (Json.Decode.nullable decodeShopCard)
+
+type alias GameCard =
+    { suit : Suit
+    , rank : Int
+    }
+
+encodeGameCard : GameCard -> Json.Encode.Value
+encodeGameCard gamecard =
+    Json.Encode.object
+        [ ( "suit", encodeSuit gamecard.suit )
+        , ( "rank", Json.Encode.int gamecard.rank )
+        ]
+
+decodeGameCard : Json.Decode.Decoder GameCard
+decodeGameCard =
+    Json.Decode.succeed GameCard
+        |> Json.Decode.Pipeline.required "suit" decodeSuit
+        |> Json.Decode.Pipeline.required "rank" Json.Decode.int
+
+type alias ShopCard =
+    { price : Int
+    , suit : Suit
+    }
+
+encodeShopCard : ShopCard -> Json.Encode.Value
+encodeShopCard shopcard =
+    Json.Encode.object
+        [ ( "price", Json.Encode.int shopcard.price )
+        , ( "suit", encodeSuit shopcard.suit )
+        ]
+
+decodeShopCard : Json.Decode.Decoder ShopCard
+decodeShopCard =
+    Json.Decode.succeed ShopCard
+        |> Json.Decode.Pipeline.required "price" Json.Decode.int
+        |> Json.Decode.Pipeline.required "suit" decodeSuit
+
+type Suit
+    = Hearts
+    | Spades
+
+encodeSuit : Suit -> Json.Encode.Value
+encodeSuit suit =
+    case suit of
+        Hearts ->
+            Json.Encode.string "Hearts"
+
+        Spades ->
+            Json.Encode.string "Spades"
+
+
+decodeSuit : Json.Decode.Decoder Suit
+decodeSuit =
+    Json.Decode.oneOf
+        [ decodeSuitHearts
+        , decodeSuitSpades
+        ]
+
+
+decodeSuitHearts : Json.Decode.Decoder Suit
+decodeSuitHearts =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "Hearts" ->
+                    Json.Decode.succeed Hearts
+
+                _ ->
+                    Json.Decode.fail "Expected variant Hearts"
+        )
+        Json.Decode.string
+
+
+decodeSuitSpades : Json.Decode.Decoder Suit
+decodeSuitSpades =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "Spades" ->
+                    Json.Decode.succeed Spades
+
+                _ ->
+                    Json.Decode.fail "Expected variant Spades"
+        )
+        Json.Decode.string
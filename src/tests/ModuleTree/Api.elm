@@ -0,0 +1,26 @@
+module Api exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+import Api.Game
+import Api.Shop
+
+type alias Table =
+    { players : (List Api.Game.Player)
+    , offers : (List Api.Shop.Offer)
+    }
+
+encodeTable : Table -> Json.Encode.Value
+encodeTable table =
+    Json.Encode.object
+        [ ( "players", (Json.Encode.list Api.Game.encodePlayer) table.players )
+        , ( "offers", (Json.Encode.list Api.Shop.encodeOffer) table.offers )
+        ]
+
+decodeTable : Json.Decode.Decoder Table
+decodeTable =
+    Json.Decode.succeed Table
+        |> Json.Decode.Pipeline.required "players" (Json.Decode.list Api.Game.decodePlayer)
+        |> Json.Decode.Pipeline.required "offers" (Json.Decode.list Api.Shop.decodeOffer)
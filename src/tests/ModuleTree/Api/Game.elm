@@ -0,0 +1,28 @@
+module Api.Game exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+import Api.Game.Cards
+
+type alias Player =
+    { name : String
+    , hand : (List Api.Game.Cards.Card)
+    , discarded : (Api.Game.Cards.Pile Api.Game.Cards.Card)
+    }
+
+encodePlayer : Player -> Json.Encode.Value
+encodePlayer player =
+    Json.Encode.object
+        [ ( "name", Json.Encode.string player.name )
+        , ( "hand", (Json.Encode.list Api.Game.Cards.encodeCard) player.hand )
+        , ( "discarded", (Api.Game.Cards.encodePile Api.Game.Cards.encodeCard) player.discarded )
+        ]
+
+decodePlayer : Json.Decode.Decoder Player
+decodePlayer =
+    Json.Decode.succeed Player
+        |> Json.Decode.Pipeline.required "name" Json.Decode.string
+        |> Json.Decode.Pipeline.required "hand" (Json.Decode.list Api.Game.Cards.decodeCard)
+        |> Json.Decode.Pipeline.required "discarded" (Api.Game.Cards.decodePile Api.Game.Cards.decodeCard)
@@ -0,0 +1,88 @@
+module Api.Game.Cards exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Card =
+    { suit : Suit
+    , rank : Int
+    }
+
+encodeCard : Card -> Json.Encode.Value
+encodeCard card =
+    Json.Encode.object
+        [ ( "suit", encodeSuit card.suit )
+        , ( "rank", Json.Encode.int card.rank )
+        ]
+
+decodeCard : Json.Decode.Decoder Card
+decodeCard =
+    Json.Decode.succeed Card
+        |> Json.Decode.Pipeline.required "suit" decodeSuit
+        |> Json.Decode.Pipeline.required "rank" Json.Decode.int
+
+type alias Pile t =
+    { cards : (List t)
+    }
+
+encodePile : (t -> Json.Encode.Value) -> Pile t -> Json.Encode.Value
+encodePile encodeT pile =
+    Json.Encode.object
+        [ ( "cards", (Json.Encode.list encodeT) pile.cards )
+        ]
+
+decodePile : Json.Decode.Decoder t -> Json.Decode.Decoder (Pile t)
+decodePile decodeT =
+    Json.Decode.succeed Pile
+        |> Json.Decode.Pipeline.required "cards" (Json.Decode.list decodeT)
+
+type Suit
+    = Hearts
+    | Spades
+
+encodeSuit : Suit -> Json.Encode.Value
+encodeSuit suit =
+    case suit of
+        Hearts ->
+            Json.Encode.string "Hearts"
+
+        Spades ->
+            Json.Encode.string "Spades"
+
+
+decodeSuit : Json.Decode.Decoder Suit
+decodeSuit =
+    Json.Decode.oneOf
+        [ decodeSuitHearts
+        , decodeSuitSpades
+        ]
+
+
+decodeSuitHearts : Json.Decode.Decoder Suit
+decodeSuitHearts =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "Hearts" ->
+                    Json.Decode.succeed Hearts
+
+                _ ->
+                    Json.Decode.fail "Expected variant Hearts"
+        )
+        Json.Decode.string
+
+
+decodeSuitSpades : Json.Decode.Decoder Suit
+decodeSuitSpades =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "Spades" ->
+                    Json.Decode.succeed Spades
+
+                _ ->
+                    Json.Decode.fail "Expected variant Spades"
+        )
+        Json.Decode.string
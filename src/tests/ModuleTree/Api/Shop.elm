@@ -0,0 +1,70 @@
+module Api.Shop exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+import Api.Game.Cards
+
+type alias Card =
+    { price : Int
+    , card : Api.Game.Cards.Card
+    }
+
+encodeCard : Card -> Json.Encode.Value
+encodeCard card =
+    Json.Encode.object
+        [ ( "price", Json.Encode.int card.price )
+        , ( "card", Api.Game.Cards.encodeCard card.card )
+        ]
+
+decodeCard : Json.Decode.Decoder Card
+decodeCard =
+    Json.Decode.succeed Card
+        |> Json.Decode.Pipeline.required "price" Json.Decode.int
+        |> Json.Decode.Pipeline.required "card" Api.Game.Cards.decodeCard
+
+type Offer
+    = Single Card
+    | Bundle { cards : (List Api.Game.Cards.Card), price : Int }
+
+encodeOffer : Offer -> Json.Encode.Value
+encodeOffer offer =
+    case offer of
+        Single x ->
+            Json.Encode.object
+                [ ( "Single", encodeCard x )
+                ]
+
+        Bundle x ->
+            Json.Encode.object
+                [ ( "Bundle"
+                  , Json.Encode.object
+                        [ ( "cards", (Json.Encode.list Api.Game.Cards.encodeCard) x.cards )
+                        , ( "price", Json.Encode.int x.price )
+                        ]
+                  )
+                ]
+
+
+decodeOffer : Json.Decode.Decoder Offer
+decodeOffer =
+    Json.Decode.oneOf
+        [ decodeOfferSingle
+        , decodeOfferBundle
+        ]
+
+
+decodeOfferSingle : Json.Decode.Decoder Offer
+decodeOfferSingle =
+    Json.Decode.succeed Single
+        |> Json.Decode.Pipeline.required "Single" decodeCard
+
+
+decodeOfferBundle : Json.Decode.Decoder Offer
+decodeOfferBundle =
+    Json.Decode.field "Bundle"
+        (Json.Decode.succeed (\cards price -> Bundle { cards = cards, price = price })
+            |> Json.Decode.Pipeline.required "cards" (Json.Decode.list Api.Game.Cards.decodeCard)
+            |> Json.Decode.Pipeline.required "price" Json.Decode.int
+        )
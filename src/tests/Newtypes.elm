@@ -0,0 +1,95 @@
+module Newtypes exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Account =
+    { id : UserId
+    , email : Email
+    , position : Point
+    , marker : Marker
+    , friends : (List (Tagged UserId))
+    }
+
+encodeAccount : Account -> Json.Encode.Value
+encodeAccount account =
+    Json.Encode.object
+        [ ( "id", encodeUserId account.id )
+        , ( "email", encodeEmail account.email )
+        , ( "position", encodePoint account.position )
+        , ( "marker", encodeMarker account.marker )
+        , ( "friends", (Json.Encode.list (encodeTagged encodeUserId)) account.friends )
+        ]
+
+decodeAccount : Json.Decode.Decoder Account
+decodeAccount =
+    Json.Decode.succeed Account
+        |> Json.Decode.Pipeline.required "id" decodeUserId
+        |> Json.Decode.Pipeline.required "email" decodeEmail
+        |> Json.Decode.Pipeline.required "position" decodePoint
+        |> Json.Decode.Pipeline.required "marker" decodeMarker
+        |> Json.Decode.Pipeline.required "friends" (Json.Decode.list (decodeTagged decodeUserId))
+
+type alias UserId =
+    Int
+
+encodeUserId : UserId -> Json.Encode.Value
+encodeUserId userid =
+    Json.Encode.int userid
+
+decodeUserId : Json.Decode.Decoder UserId
+decodeUserId =
+    Json.Decode.int
+
+type Email
+    = Email String
+
+encodeEmail : Email -> Json.Encode.Value
+encodeEmail (Email x) =
+    Json.Encode.string x
+
+decodeEmail : Json.Decode.Decoder Email
+decodeEmail =
+    Json.Decode.map Email Json.Decode.string
+
+type Point
+    = Point Int Int (Maybe String)
+
+encodePoint : Point -> Json.Encode.Value
+encodePoint (Point x0 x1 x2) =
+    Json.Encode.list identity
+        [ Json.Encode.int x0
+        , Json.Encode.int x1
+        , (Maybe.map Json.Encode.string >> Maybe.withDefault Json.Encode.null) x2
+        ]
+
+decodePoint : Json.Decode.Decoder Point
+decodePoint =
+    Json.Decode.succeed Point
+        |> Json.Decode.Pipeline.custom (Json.Decode.index 0 Json.Decode.int)
+        |> Json.Decode.Pipeline.custom (Json.Decode.index 1 Json.Decode.int)
+        |> Json.Decode.Pipeline.custom (Json.Decode.index 2 (Json.Decode.nullable Json.Decode.string))
+
+type Marker
+    = Marker
+
+encodeMarker : Marker -> Json.Encode.Value
+encodeMarker _ =
+    Json.Encode.null
+
+decodeMarker : Json.Decode.Decoder Marker
+decodeMarker =
+    Json.Decode.null Marker
+
+type Tagged t
+    = Tagged t
+
+encodeTagged : (t -> Json.Encode.Value) -> Tagged t -> Json.Encode.Value
+encodeTagged encodeT (Tagged x) =
+    encodeT x
+
+decodeTagged : Json.Decode.Decoder t -> Json.Decode.Decoder (Tagged t)
+decodeTagged decodeT =
+    Json.Decode.map Tagged decodeT
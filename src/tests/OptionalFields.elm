@@ -0,0 +1,118 @@
+module OptionalFields exposing (..)
+
+
+import Dict exposing (Dict)
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Preferences =
+    { settings : Settings
+    , window : Window
+    }
+
+encodePreferences : Preferences -> Json.Encode.Value
+encodePreferences preferences =
+    Json.Encode.object
+        [ ( "settings", encodeSettings preferences.settings )
+        , ( "window", encodeWindow preferences.window )
+        ]
+
+decodePreferences : Json.Decode.Decoder Preferences
+decodePreferences =
+    Json.Decode.succeed Preferences
+        |> Json.Decode.Pipeline.required "settings" decodeSettings
+        |> Json.Decode.Pipeline.required "window" decodeWindow
+
+type alias Settings =
+    { name : String
+    , nickname : (Maybe String)
+    , volume : Int
+    , tags : (List String)
+    , limits : (Dict Int String)
+    , theme : (Maybe Theme)
+    }
+
+encodeSettings : Settings -> Json.Encode.Value
+encodeSettings settings =
+    Json.Encode.object
+        (List.filterMap identity
+            [ Just ( "name", Json.Encode.string settings.name )
+            , Maybe.map (Tuple.pair "nickname" << Json.Encode.string) settings.nickname
+            , Just ( "volume", Json.Encode.int settings.volume )
+            , Just ( "tags", (Json.Encode.list Json.Encode.string) settings.tags )
+            , Just ( "limits", (Json.Encode.dict String.fromInt Json.Encode.string) settings.limits )
+            , Maybe.map (Tuple.pair "theme" << encodeTheme) settings.theme
+            ]
+        )
+
+decodeSettings : Json.Decode.Decoder Settings
+decodeSettings =
+    Json.Decode.succeed Settings
+        |> Json.Decode.Pipeline.required "name" Json.Decode.string
+        |> Json.Decode.Pipeline.optional "nickname" (Json.Decode.nullable Json.Decode.string) Nothing
+        |> Json.Decode.Pipeline.optional "volume" Json.Decode.int 0
+        |> Json.Decode.Pipeline.optional "tags" (Json.Decode.list Json.Decode.string) []
+        |> Json.Decode.Pipeline.optional "limits" (decodeDictWithKeys String.toInt Json.Decode.string) Dict.empty
+        |> Json.Decode.Pipeline.optional "theme" (Json.Decode.nullable decodeTheme) Nothing
+
+type alias Window =
+    { width : Int
+    , title : (Maybe String)
+    }
+
+encodeWindow : Window -> Json.Encode.Value
+encodeWindow window =
+    Json.Encode.object
+        (List.filterMap identity
+            [ Just ( "width", Json.Encode.int window.width )
+            , Maybe.map (Tuple.pair "title" << Json.Encode.string) window.title
+            ]
+        )
+
+decodeWindow : Json.Decode.Decoder Window
+decodeWindow =
+    Json.Decode.succeed Window
+        |> Json.Decode.Pipeline.optional "width" Json.Decode.int 0
+        |> Json.Decode.Pipeline.optional "title" (Json.Decode.nullable Json.Decode.string) Nothing
+
+type alias Theme =
+    { dark : Bool
+    }
+
+encodeTheme : Theme -> Json.Encode.Value
+encodeTheme theme =
+    Json.Encode.object
+        [ ( "dark", Json.Encode.bool theme.dark )
+        ]
+
+decodeTheme : Json.Decode.Decoder Theme
+decodeTheme =
+    Json.Decode.succeed Theme
+        |> Json.Decode.Pipeline.required "dark" Json.Decode.bool
+
+decodeDictWithKeys : (String -> Maybe comparable) -> Json.Decode.Decoder v -> Json.Decode.Decoder (Dict comparable v)
+decodeDictWithKeys keyFromString valueDecoder =
+    Json.Decode.map Dict.fromList (decodeKeyValueList keyFromString valueDecoder)
+
+-- Elm can only use comparable types like Int and String as Dict keys.
+-- Maps with other keys, like enums, are represented as List ( k, v ).
+decodeKeyValueList : (String -> Maybe k) -> Json.Decode.Decoder v -> Json.Decode.Decoder (List ( k, v ))
+decodeKeyValueList keyFromString valueDecoder =
+    let
+        parseEntry ( key, value ) =
+            Maybe.map (\k -> ( k, value )) (keyFromString key)
+    in
+    Json.Decode.keyValuePairs valueDecoder
+        |> Json.Decode.andThen
+            (\pairs ->
+                let
+                    entries =
+                        List.filterMap parseEntry pairs
+                in
+                if List.length entries == List.length pairs then
+                    Json.Decode.succeed entries
+
+                else
+                    Json.Decode.fail "Unexpected map key"
+            )
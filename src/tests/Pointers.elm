@@ -0,0 +1,39 @@
+module Pointers exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Shared =
+    { boxed : Int
+    , counted : String
+    , atomic : (List Bool)
+    , borrowed : String
+    , cow : String
+    , boxedStr : String
+    , cowSlice : (List Int)
+    }
+
+encodeShared : Shared -> Json.Encode.Value
+encodeShared shared =
+    Json.Encode.object
+        [ ( "boxed", Json.Encode.int shared.boxed )
+        , ( "counted", Json.Encode.string shared.counted )
+        , ( "atomic", (Json.Encode.list Json.Encode.bool) shared.atomic )
+        , ( "borrowed", Json.Encode.string shared.borrowed )
+        , ( "cow", Json.Encode.string shared.cow )
+        , ( "boxed_str", Json.Encode.string shared.boxedStr )
+        , ( "cow_slice", (Json.Encode.list Json.Encode.int) shared.cowSlice )
+        ]
+
+decodeShared : Json.Decode.Decoder Shared
+decodeShared =
+    Json.Decode.succeed Shared
+        |> Json.Decode.Pipeline.required "boxed" Json.Decode.int
+        |> Json.Decode.Pipeline.required "counted" Json.Decode.string
+        |> Json.Decode.Pipeline.required "atomic" (Json.Decode.list Json.Decode.bool)
+        |> Json.Decode.Pipeline.required "borrowed" Json.Decode.string
+        |> Json.Decode.Pipeline.required "cow" Json.Decode.string
+        |> Json.Decode.Pipeline.required "boxed_str" Json.Decode.string
+        |> Json.Decode.Pipeline.required "cow_slice" (Json.Decode.list Json.Decode.int)
@@ -0,0 +1,141 @@
+module Renames exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Profile =
+    { displayName : String
+    , emailAddress : String
+    , status : Status
+    , lastAction : Action
+    }
+
+encodeProfile : Profile -> Json.Encode.Value
+encodeProfile profile =
+    Json.Encode.object
+        [ ( "displayName", Json.Encode.string profile.displayName )
+        , ( "e-mail", Json.Encode.string profile.emailAddress )
+        , ( "status", encodeStatus profile.status )
+        , ( "lastAction", encodeAction profile.lastAction )
+        ]
+
+decodeProfile : Json.Decode.Decoder Profile
+decodeProfile =
+    Json.Decode.succeed Profile
+        |> Json.Decode.Pipeline.required "displayName" Json.Decode.string
+        |> Json.Decode.Pipeline.required "e-mail" Json.Decode.string
+        |> Json.Decode.Pipeline.required "status" decodeStatus
+        |> Json.Decode.Pipeline.required "lastAction" decodeAction
+
+type Status
+    = OnlineNow
+    | Away
+    | Busy String
+
+encodeStatus : Status -> Json.Encode.Value
+encodeStatus status =
+    case status of
+        OnlineNow ->
+            Json.Encode.string "ONLINE_NOW"
+
+        Away ->
+            Json.Encode.string "away"
+
+        Busy x ->
+            Json.Encode.object
+                [ ( "BUSY", Json.Encode.string x )
+                ]
+
+
+decodeStatus : Json.Decode.Decoder Status
+decodeStatus =
+    Json.Decode.oneOf
+        [ decodeStatusOnlineNow
+        , decodeStatusAway
+        , decodeStatusBusy
+        ]
+
+
+decodeStatusOnlineNow : Json.Decode.Decoder Status
+decodeStatusOnlineNow =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "ONLINE_NOW" ->
+                    Json.Decode.succeed OnlineNow
+
+                _ ->
+                    Json.Decode.fail "Expected variant ONLINE_NOW"
+        )
+        Json.Decode.string
+
+
+decodeStatusAway : Json.Decode.Decoder Status
+decodeStatusAway =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "away" ->
+                    Json.Decode.succeed Away
+
+                _ ->
+                    Json.Decode.fail "Expected variant away"
+        )
+        Json.Decode.string
+
+
+decodeStatusBusy : Json.Decode.Decoder Status
+decodeStatusBusy =
+    Json.Decode.succeed Busy
+        |> Json.Decode.Pipeline.required "BUSY" Json.Decode.string
+
+type Action
+    = LoggedIn { userName : String }
+    | LoggedOut { userName : String }
+
+encodeAction : Action -> Json.Encode.Value
+encodeAction action =
+    case action of
+        LoggedIn x ->
+            Json.Encode.object
+                [ ( "logged-in"
+                  , Json.Encode.object
+                        [ ( "UserName", Json.Encode.string x.userName )
+                        ]
+                  )
+                ]
+
+        LoggedOut x ->
+            Json.Encode.object
+                [ ( "logged-out"
+                  , Json.Encode.object
+                        [ ( "userName", Json.Encode.string x.userName )
+                        ]
+                  )
+                ]
+
+
+decodeAction : Json.Decode.Decoder Action
+decodeAction =
+    Json.Decode.oneOf
+        [ decodeActionLoggedIn
+        , decodeActionLoggedOut
+        ]
+
+
+decodeActionLoggedIn : Json.Decode.Decoder Action
+decodeActionLoggedIn =
+    Json.Decode.field "logged-in"
+        (Json.Decode.succeed (\userName -> LoggedIn { userName = userName })
+            |> Json.Decode.Pipeline.required "UserName" Json.Decode.string
+        )
+
+
+decodeActionLoggedOut : Json.Decode.Decoder Action
+decodeActionLoggedOut =
+    Json.Decode.field "logged-out"
+        (Json.Decode.succeed (\userName -> LoggedOut { userName = userName })
+            |> Json.Decode.Pipeline.required "userName" Json.Decode.string
+        )
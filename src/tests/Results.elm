@@ -0,0 +1,98 @@
+module Results exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Response =
+    { result : (Result.Result ApiError Payload)
+    , checks : (List (Result.Result String ()))
+    }
+
+encodeResponse : Response -> Json.Encode.Value
+encodeResponse response =
+    Json.Encode.object
+        [ ( "result", (encodeStdResult encodeApiError encodePayload) response.result )
+        , ( "checks", (Json.Encode.list (encodeStdResult Json.Encode.string (always Json.Encode.null))) response.checks )
+        ]
+
+decodeResponse : Json.Decode.Decoder Response
+decodeResponse =
+    Json.Decode.succeed Response
+        |> Json.Decode.Pipeline.required "result" (decodeStdResult decodeApiError decodePayload)
+        |> Json.Decode.Pipeline.required "checks" (Json.Decode.list (decodeStdResult Json.Decode.string (Json.Decode.null ())))
+
+type alias Payload =
+    { value : Int
+    }
+
+encodePayload : Payload -> Json.Encode.Value
+encodePayload payload =
+    Json.Encode.object
+        [ ( "value", Json.Encode.int payload.value )
+        ]
+
+decodePayload : Json.Decode.Decoder Payload
+decodePayload =
+    Json.Decode.succeed Payload
+        |> Json.Decode.Pipeline.required "value" Json.Decode.int
+
+type ApiError
+    = NotFound
+    | Invalid String
+
+encodeApiError : ApiError -> Json.Encode.Value
+encodeApiError apierror =
+    case apierror of
+        NotFound ->
+            Json.Encode.string "NotFound"
+
+        Invalid x ->
+            Json.Encode.object
+                [ ( "Invalid", Json.Encode.string x )
+                ]
+
+
+decodeApiError : Json.Decode.Decoder ApiError
+decodeApiError =
+    Json.Decode.oneOf
+        [ decodeApiErrorNotFound
+        , decodeApiErrorInvalid
+        ]
+
+
+decodeApiErrorNotFound : Json.Decode.Decoder ApiError
+decodeApiErrorNotFound =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "NotFound" ->
+                    Json.Decode.succeed NotFound
+
+                _ ->
+                    Json.Decode.fail "Expected variant NotFound"
+        )
+        Json.Decode.string
+
+
+decodeApiErrorInvalid : Json.Decode.Decoder ApiError
+decodeApiErrorInvalid =
+    Json.Decode.succeed Invalid
+        |> Json.Decode.Pipeline.required "Invalid" Json.Decode.string
+
+decodeStdResult : Json.Decode.Decoder e -> Json.Decode.Decoder t -> Json.Decode.Decoder (Result.Result e t)
+decodeStdResult decodeErr decodeOk =
+    Json.Decode.oneOf
+        [ Json.Decode.map Result.Ok (Json.Decode.field "Ok" decodeOk)
+        , Json.Decode.map Result.Err (Json.Decode.field "Err" decodeErr)
+        ]
+
+encodeStdResult : (e -> Json.Encode.Value) -> (t -> Json.Encode.Value) -> Result.Result e t -> Json.Encode.Value
+encodeStdResult encodeErr encodeOk result =
+    case result of
+        Result.Ok value ->
+            Json.Encode.object [ ( "Ok", encodeOk value ) ]
+
+        Result.Err error ->
+            Json.Encode.object [ ( "Err", encodeErr error ) ]
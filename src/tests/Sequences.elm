@@ -0,0 +1,51 @@
+module Sequences exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Collections =
+    { fixed : (List Int)
+    , boxed : (List String)
+    , queue : (List Int)
+    , linked : (List Bool)
+    , ordered : (List Int)
+    , unique : (List String)
+    }
+
+encodeCollections : Collections -> Json.Encode.Value
+encodeCollections collections =
+    Json.Encode.object
+        [ ( "fixed", (Json.Encode.list Json.Encode.int) collections.fixed )
+        , ( "boxed", (Json.Encode.list Json.Encode.string) collections.boxed )
+        , ( "queue", (Json.Encode.list Json.Encode.int) collections.queue )
+        , ( "linked", (Json.Encode.list Json.Encode.bool) collections.linked )
+        , ( "ordered", (Json.Encode.list Json.Encode.int) collections.ordered )
+        , ( "unique", (Json.Encode.list Json.Encode.string) collections.unique )
+        ]
+
+decodeCollections : Json.Decode.Decoder Collections
+decodeCollections =
+    Json.Decode.succeed Collections
+        |> Json.Decode.Pipeline.required "fixed" (Json.Decode.list Json.Decode.int)
+        |> Json.Decode.Pipeline.required "boxed" (Json.Decode.list Json.Decode.string)
+        |> Json.Decode.Pipeline.required "queue" (Json.Decode.list Json.Decode.int)
+        |> Json.Decode.Pipeline.required "linked" (Json.Decode.list Json.Decode.bool)
+        |> Json.Decode.Pipeline.required "ordered" (Json.Decode.list Json.Decode.int)
+        |> Json.Decode.Pipeline.required "unique" (Json.Decode.list Json.Decode.string)
+
+type alias Borrowed =
+    { values : (List Int)
+    }
+
+encodeBorrowed : Borrowed -> Json.Encode.Value
+encodeBorrowed borrowed =
+    Json.Encode.object
+        [ ( "values", (Json.Encode.list Json.Encode.int) borrowed.values )
+        ]
+
+decodeBorrowed : Json.Decode.Decoder Borrowed
+decodeBorrowed =
+    Json.Decode.succeed Borrowed
+        |> Json.Decode.Pipeline.required "values" (Json.Decode.list Json.Decode.int)
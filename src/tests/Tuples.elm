@@ -0,0 +1,85 @@
+module Tuples exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Shapes =
+    { pair : ( Int, String )
+    , triple : ( Bool, Float, String )
+    , quad : (Tuple4 Int Int Int Int)
+    , points : (List ( Int, Int ))
+    , nothing : ()
+    }
+
+encodeShapes : Shapes -> Json.Encode.Value
+encodeShapes shapes =
+    Json.Encode.object
+        [ ( "pair", (encodeTuple2 Json.Encode.int Json.Encode.string) shapes.pair )
+        , ( "triple", (encodeTuple3 Json.Encode.bool Json.Encode.float Json.Encode.string) shapes.triple )
+        , ( "quad", (encodeTuple4 Json.Encode.int Json.Encode.int Json.Encode.int Json.Encode.int) shapes.quad )
+        , ( "points", (Json.Encode.list (encodeTuple2 Json.Encode.int Json.Encode.int)) shapes.points )
+        , ( "nothing", (always Json.Encode.null) shapes.nothing )
+        ]
+
+decodeShapes : Json.Decode.Decoder Shapes
+decodeShapes =
+    Json.Decode.succeed Shapes
+        |> Json.Decode.Pipeline.required "pair" (decodeTuple2 Json.Decode.int Json.Decode.string)
+        |> Json.Decode.Pipeline.required "triple" (decodeTuple3 Json.Decode.bool Json.Decode.float Json.Decode.string)
+        |> Json.Decode.Pipeline.required "quad" (decodeTuple4 Json.Decode.int Json.Decode.int Json.Decode.int Json.Decode.int)
+        |> Json.Decode.Pipeline.required "points" (Json.Decode.list (decodeTuple2 Json.Decode.int Json.Decode.int))
+        |> Json.Decode.Pipeline.required "nothing" (Json.Decode.null ())
+
+decodeTuple2 : Json.Decode.Decoder a -> Json.Decode.Decoder b -> Json.Decode.Decoder ( a, b )
+decodeTuple2 decodeA decodeB =
+    Json.Decode.map2 Tuple.pair
+        (Json.Decode.index 0 decodeA)
+        (Json.Decode.index 1 decodeB)
+
+encodeTuple2 : (a -> Json.Encode.Value) -> (b -> Json.Encode.Value) -> ( a, b ) -> Json.Encode.Value
+encodeTuple2 encodeA encodeB ( a, b ) =
+    Json.Encode.list identity
+        [ encodeA a
+        , encodeB b
+        ]
+
+decodeTuple3 : Json.Decode.Decoder a -> Json.Decode.Decoder b -> Json.Decode.Decoder c -> Json.Decode.Decoder ( a, b, c )
+decodeTuple3 decodeA decodeB decodeC =
+    Json.Decode.map3 (\a b c -> ( a, b, c ))
+        (Json.Decode.index 0 decodeA)
+        (Json.Decode.index 1 decodeB)
+        (Json.Decode.index 2 decodeC)
+
+encodeTuple3 : (a -> Json.Encode.Value) -> (b -> Json.Encode.Value) -> (c -> Json.Encode.Value) -> ( a, b, c ) -> Json.Encode.Value
+encodeTuple3 encodeA encodeB encodeC ( a, b, c ) =
+    Json.Encode.list identity
+        [ encodeA a
+        , encodeB b
+        , encodeC c
+        ]
+
+type alias Tuple4 a b c d =
+    { item0 : a
+    , item1 : b
+    , item2 : c
+    , item3 : d
+    }
+
+decodeTuple4 : Json.Decode.Decoder a -> Json.Decode.Decoder b -> Json.Decode.Decoder c -> Json.Decode.Decoder d -> Json.Decode.Decoder (Tuple4 a b c d)
+decodeTuple4 decodeA decodeB decodeC decodeD =
+    Json.Decode.succeed Tuple4
+        |> Json.Decode.Pipeline.custom (Json.Decode.index 0 decodeA)
+        |> Json.Decode.Pipeline.custom (Json.Decode.index 1 decodeB)
+        |> Json.Decode.Pipeline.custom (Json.Decode.index 2 decodeC)
+        |> Json.Decode.Pipeline.custom (Json.Decode.index 3 decodeD)
+
+encodeTuple4 : (a -> Json.Encode.Value) -> (b -> Json.Encode.Value) -> (c -> Json.Encode.Value) -> (d -> Json.Encode.Value) -> (Tuple4 a b c d) -> Json.Encode.Value
+encodeTuple4 encodeA encodeB encodeC encodeD tuple =
+    Json.Encode.list identity
+        [ encodeA tuple.item0
+        , encodeB tuple.item1
+        , encodeC tuple.item2
+        , encodeD tuple.item3
+        ]
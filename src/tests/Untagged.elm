@@ -0,0 +1,122 @@
+module Untagged exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Named =
+    { name : String
+    }
+
+encodeNamed : Named -> Json.Encode.Value
+encodeNamed named =
+    Json.Encode.object
+        [ ( "name", Json.Encode.string named.name )
+        ]
+
+decodeNamed : Json.Decode.Decoder Named
+decodeNamed =
+    Json.Decode.succeed Named
+        |> Json.Decode.Pipeline.required "name" Json.Decode.string
+
+type Value
+    = Missing
+    | Count Int
+    | Ratio Float
+    | Small Int
+    | Label String
+    | Range Int Int
+    | Person Named
+    | Employee { name : String, team : String }
+
+encodeValue : Value -> Json.Encode.Value
+encodeValue value =
+    case value of
+        Missing ->
+            Json.Encode.null
+
+        Count x ->
+            Json.Encode.int x
+
+        Ratio x ->
+            Json.Encode.float x
+
+        Small x ->
+            Json.Encode.int x
+
+        Label x ->
+            Json.Encode.string x
+
+        Range x0 x1 ->
+            Json.Encode.list identity
+                [ Json.Encode.int x0
+                , Json.Encode.int x1
+                ]
+
+        Person x ->
+            encodeNamed x
+
+        Employee x ->
+            Json.Encode.object
+                [ ( "name", Json.Encode.string x.name )
+                , ( "team", Json.Encode.string x.team )
+                ]
+
+
+decodeValue : Json.Decode.Decoder Value
+decodeValue =
+    Json.Decode.oneOf
+        [ decodeValueMissing
+        , decodeValueCount
+        , decodeValueRatio
+        , decodeValueSmall
+        , decodeValueLabel
+        , decodeValueRange
+        , decodeValuePerson
+        , decodeValueEmployee
+        ]
+
+
+decodeValueMissing : Json.Decode.Decoder Value
+decodeValueMissing =
+    Json.Decode.null Missing
+
+
+decodeValueCount : Json.Decode.Decoder Value
+decodeValueCount =
+    Json.Decode.map Count Json.Decode.int
+
+
+decodeValueRatio : Json.Decode.Decoder Value
+decodeValueRatio =
+    Json.Decode.map Ratio Json.Decode.float
+
+
+decodeValueSmall : Json.Decode.Decoder Value
+decodeValueSmall =
+    Json.Decode.map Small Json.Decode.int
+
+
+decodeValueLabel : Json.Decode.Decoder Value
+decodeValueLabel =
+    Json.Decode.map Label Json.Decode.string
+
+
+decodeValueRange : Json.Decode.Decoder Value
+decodeValueRange =
+    Json.Decode.succeed Range
+        |> Json.Decode.Pipeline.custom (Json.Decode.index 0 Json.Decode.int)
+        |> Json.Decode.Pipeline.custom (Json.Decode.index 1 Json.Decode.int)
+
+
+decodeValuePerson : Json.Decode.Decoder Value
+decodeValuePerson =
+    Json.Decode.map Person decodeNamed
+
+
+decodeValueEmployee : Json.Decode.Decoder Value
+decodeValueEmployee =
+    Json.Decode.succeed (\name team -> Employee { name = name, team = team })
+        |> Json.Decode.Pipeline.required "name" Json.Decode.string
+        |> Json.Decode.Pipeline.required "team" Json.Decode.string
@@ -0,0 +1,121 @@
+module WholeCrate exposing (..)
+
+
+import Json.Decode
+import Json.Encode
+import Json.Decode.Pipeline
+
+type alias Drawing =
+    { title : String
+    , lines : (List Line)
+    , color : Color
+    }
+
+encodeDrawing : Drawing -> Json.Encode.Value
+encodeDrawing drawing =
+    Json.Encode.object
+        [ ( "title", Json.Encode.string drawing.title )
+        , ( "lines", (Json.Encode.list encodeLine) drawing.lines )
+        , ( "color", encodeColor drawing.color )
+        ]
+
+decodeDrawing : Json.Decode.Decoder Drawing
+decodeDrawing =
+    Json.Decode.succeed Drawing
+        |> Json.Decode.Pipeline.required "title" Json.Decode.string
+        |> Json.Decode.Pipeline.required "lines" (Json.Decode.list decodeLine)
+        |> Json.Decode.Pipeline.required "color" decodeColor
+
+type alias Gallery =
+    { drawings : (List Drawing)
+    }
+
+encodeGallery : Gallery -> Json.Encode.Value
+encodeGallery gallery =
+    Json.Encode.object
+        [ ( "drawings", (Json.Encode.list encodeDrawing) gallery.drawings )
+        ]
+
+decodeGallery : Json.Decode.Decoder Gallery
+decodeGallery =
+    Json.Decode.succeed Gallery
+        |> Json.Decode.Pipeline.required "drawings" (Json.Decode.list decodeDrawing)
+
+type alias Line =
+    { from : Point
+    , to : Point
+    }
+
+encodeLine : Line -> Json.Encode.Value
+encodeLine line =
+    Json.Encode.object
+        [ ( "from", encodePoint line.from )
+        , ( "to", encodePoint line.to )
+        ]
+
+decodeLine : Json.Decode.Decoder Line
+decodeLine =
+    Json.Decode.succeed Line
+        |> Json.Decode.Pipeline.required "from" decodePoint
+        |> Json.Decode.Pipeline.required "to" decodePoint
+
+type alias Point =
+    { x : Float
+    , y : Float
+    }
+
+encodePoint : Point -> Json.Encode.Value
+encodePoint point =
+    Json.Encode.object
+        [ ( "x", Json.Encode.float point.x )
+        , ( "y", Json.Encode.float point.y )
+        ]
+
+decodePoint : Json.Decode.Decoder Point
+decodePoint =
+    Json.Decode.succeed Point
+        |> Json.Decode.Pipeline.required "x" Json.Decode.float
+        |> Json.Decode.Pipeline.required "y" Json.Decode.float
+
+type Color
+    = Black
+    | Custom String
+
+encodeColor : Color -> Json.Encode.Value
+encodeColor color =
+    case color of
+        Black ->
+            Json.Encode.string "Black"
+
+        Custom x ->
+            Json.Encode.object
+                [ ( "Custom", Json.Encode.string x )
+                ]
+
+
+decodeColor : Json.Decode.Decoder Color
+decodeColor =
+    Json.Decode.oneOf
+        [ decodeColorBlack
+        , decodeColorCustom
+        ]
+
+
+decodeColorBlack : Json.Decode.Decoder Color
+decodeColorBlack =
+    Json.Decode.andThen
+        (\str ->
+            case str of
+                "Black" ->
+                    Json.Decode.succeed Black
+
+                _ ->
+                    Json.Decode.fail "Expected variant Black"
+        )
+        Json.Decode.string
+
+
+decodeColorCustom : Json.Decode.Decoder Color
+decodeColorCustom =
+    Json.Decode.succeed Custom
+        |> Json.Decode.Pipeline.required "Custom" Json.Decode.string
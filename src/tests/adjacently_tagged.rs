@@ -0,0 +1,35 @@
+use super::ElmExport;
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+#[serde(tag = "t", content = "c")]
+enum Frame {
+    Heartbeat,
+    Text(String),
+    Cursor(u32, u32),
+    Resize { width: u32, height: u32 },
+}
+
+impl ElmExport for Frame {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::Frame;
+        let frames = vec![
+            Frame::Heartbeat,
+            Frame::Text("hi".to_string()),
+            Frame::Cursor(3, 4),
+            Frame::Resize {
+                width: 80,
+                height: 24,
+            },
+        ];
+        let serialized = serde_json::to_string(&frames).unwrap();
+        assert_eq!(
+            serialized,
+            r#"[{"t":"Heartbeat"},{"t":"Text","c":"hi"},{"t":"Cursor","c":[3,4]},{"t":"Resize","c":{"width":80,"height":24}}]"#
+        );
+    }
+}
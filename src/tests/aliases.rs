@@ -0,0 +1,96 @@
+use super::ElmExport;
+use serde::{Deserialize, Serialize};
+
+#[allow(dead_code)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Account {
+    #[serde(alias = "username", alias = "login")]
+    name: String,
+    #[serde(default, alias = "mail")]
+    email: String,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Plan {
+    #[serde(alias = "Gratis")]
+    Free,
+    #[serde(alias = "Pro")]
+    Paid(u32),
+    #[serde(other)]
+    Unknown,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type")]
+enum Notice {
+    #[serde(alias = "note")]
+    Info {
+        text: String,
+    },
+    Warning {
+        text: String,
+    },
+    #[serde(other)]
+    Unsupported,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Signup {
+    account: Account,
+    plan: Plan,
+    notices: Vec<Notice>,
+}
+
+impl ElmExport for Signup {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::{Account, Notice, Plan, Signup};
+        let signup = Signup {
+            account: Account {
+                name: "ada".to_string(),
+                email: String::new(),
+            },
+            plan: Plan::Paid(5),
+            notices: vec![Notice::Info {
+                text: "hi".to_string(),
+            }],
+        };
+        let serialized = serde_json::to_string(&signup).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"account":{"name":"ada","email":""},"plan":{"Paid":5},"notices":[{"type":"Info","text":"hi"}]}"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_aliases() {
+        use super::{Account, Notice, Plan, Signup};
+        let json = r#"{"account":{"login":"ada"},"plan":"Gratis","notices":[{"type":"note","text":"hi"},{"type":"Error"}]}"#;
+        let signup: Signup = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            signup,
+            Signup {
+                account: Account {
+                    name: "ada".to_string(),
+                    email: String::new(),
+                },
+                plan: Plan::Free,
+                notices: vec![
+                    Notice::Info {
+                        text: "hi".to_string()
+                    },
+                    Notice::Unsupported
+                ],
+            }
+        );
+        let plan: Plan = serde_json::from_str(r#""Enterprise""#).unwrap();
+        assert_eq!(plan, Plan::Unknown);
+        let plan: Plan = serde_json::from_str(r#"{"Pro":3}"#).unwrap();
+        assert_eq!(plan, Plan::Paid(3));
+    }
+}
@@ -0,0 +1,62 @@
+use super::ElmExport;
+use serde::{Serialize, Serializer};
+use serde_repr::Serialize_repr;
+
+/// Written as its discriminant by hand, so it has to say so.
+#[allow(dead_code)]
+#[derive(Clone, Copy, ElmExport)]
+#[elm(integer)]
+#[repr(u8)]
+enum Color {
+    Red = 1,
+    Green = 2,
+    Blue,
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Serialize_repr)]
+#[repr(i8)]
+enum Level {
+    Low = -1,
+    Normal,
+    High = 5,
+}
+
+/// The derived impl writes the variant name, the repr doesn't matter.
+#[allow(dead_code)]
+#[derive(Serialize)]
+#[repr(u8)]
+enum Size {
+    Small = 1,
+    Large = 2,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Pixel {
+    color: Color,
+    level: Level,
+    size: Size,
+}
+
+impl ElmExport for Pixel {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::{Color, Level, Pixel, Size};
+        let pixel = Pixel {
+            color: Color::Blue,
+            level: Level::Low,
+            size: Size::Large,
+        };
+        let serialized = serde_json::to_string(&pixel).unwrap();
+        assert_eq!(serialized, r#"{"color":3,"level":-1,"size":"Large"}"#);
+    }
+}
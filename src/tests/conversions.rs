@@ -0,0 +1,94 @@
+use super::ElmExport;
+use serde::{Deserialize, Serialize};
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Meters {
+    value: f64,
+    #[serde(skip)]
+    cached_label: Option<String>,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Tags(Vec<String>);
+
+#[allow(dead_code)]
+#[derive(Serialize, Clone)]
+#[serde(into = "String")]
+enum Color {
+    Red,
+    Custom(u8, u8, u8),
+}
+
+impl From<Color> for String {
+    fn from(color: Color) -> String {
+        match color {
+            Color::Red => "#ff0000".to_string(),
+            Color::Custom(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Serialize, Deserialize, Clone)]
+struct RawTemperature {
+    kelvin: f64,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(from = "RawTemperature", into = "RawTemperature")]
+struct Temperature {
+    celsius: f64,
+}
+
+impl From<RawTemperature> for Temperature {
+    fn from(raw: RawTemperature) -> Temperature {
+        Temperature {
+            celsius: raw.kelvin - 273.0,
+        }
+    }
+}
+
+impl From<Temperature> for RawTemperature {
+    fn from(temperature: Temperature) -> RawTemperature {
+        RawTemperature {
+            kelvin: temperature.celsius + 273.0,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Reading {
+    distance: Meters,
+    tags: Tags,
+    color: Color,
+    temperature: Temperature,
+}
+
+impl ElmExport for Reading {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::{Color, Meters, Reading, Tags, Temperature};
+        let reading = Reading {
+            distance: Meters {
+                value: 1.5,
+                cached_label: None,
+            },
+            tags: Tags(vec!["outside".to_string()]),
+            color: Color::Custom(0, 128, 255),
+            temperature: Temperature { celsius: 20.0 },
+        };
+        let serialized = serde_json::to_string(&reading).unwrap();
+        assert_eq!(
+            serialized,
+            r##"{"distance":1.5,"tags":["outside"],"color":"#0080ff","temperature":{"kelvin":293.0}}"##
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use super::ElmExport;
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize, ElmExport)]
+#[elm(opaque)]
+struct SessionToken(String);
+
+#[allow(dead_code)]
+#[derive(Serialize, ElmExport)]
+struct Login {
+    user: String,
+    token: SessionToken,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize, ElmExport)]
+enum Presence {
+    Online,
+    Away { since: u64 },
+}
+
+#[allow(dead_code)]
+#[derive(Serialize, ElmExport)]
+enum Reply {
+    Text(String),
+    Thread(Vec<Reply>),
+}
+
+#[allow(dead_code)]
+#[derive(Serialize, ElmExport)]
+struct Paged<T> {
+    items: Vec<T>,
+    total: u32,
+}
+
+mod test {
+    use super::{ElmExport, Login, Paged, Presence, Reply, SessionToken};
+    use crate::tests::read_file_for_test;
+    use crate::{ElmDefinitions, ElmType, Identifier, TypeGenOptions};
+
+    fn assert_exported<T: ElmExport>() {}
+
+    #[test]
+    fn test_derive_implements_trait() {
+        assert_exported::<SessionToken>();
+        assert_exported::<Login>();
+        assert_exported::<Presence>();
+        assert_exported::<Paged<Login>>();
+    }
+
+    #[test]
+    fn test_elm_type() {
+        let login = ElmType::NamedType(Identifier::new("Login"), vec![]);
+        assert_eq!(
+            Paged::<Login>::elm_type(),
+            ElmType::NamedType(Identifier::new("Paged"), vec![login])
+        );
+    }
+
+    #[test]
+    fn test_runtime_definitions() {
+        let mut definitions = ElmDefinitions::default();
+        definitions.export::<SessionToken>();
+        definitions.export::<Login>();
+        definitions.export::<Presence>();
+        definitions.export::<Reply>();
+        definitions.export::<Paged<Login>>();
+        let elm_file = definitions.into_elm_file("Derive").unwrap();
+
+        let elm_file_content = read_file_for_test("src/tests/Derive.elm");
+        assert_eq!(elm_file.generate_file_content(), elm_file_content);
+    }
+
+    #[test]
+    fn test_field_types_use_their_impl() {
+        type UserId = u64;
+
+        /// Seconds since the epoch.
+        #[allow(dead_code)]
+        struct Timestamp(u64);
+
+        impl ElmExport for Timestamp {
+            fn elm_type() -> ElmType {
+                ElmType::Int
+            }
+        }
+
+        #[allow(dead_code)]
+        #[derive(ElmExport)]
+        struct Comment {
+            author: UserId,
+            posted: Timestamp,
+            initial: char,
+            stars: [u8; 5],
+        }
+
+        let mut definitions = ElmDefinitions::default();
+        definitions.export::<Comment>();
+        let content = definitions.into_elm_file("Comment").unwrap();
+        assert!(content.generate_file_content().contains(
+            "    { author : Int\n    , posted : Int\n    , initial : String\n    , stars : (List Int)\n    }"
+        ));
+
+        let options = TypeGenOptions {
+            check_array_length: true,
+            ..TypeGenOptions::default()
+        };
+        let mut definitions = ElmDefinitions::new(options);
+        definitions.export::<Comment>();
+        let content = definitions.into_elm_file("Comment").unwrap();
+        assert!(content
+            .generate_file_content()
+            .contains("(decodeListOfLength 5 Json.Decode.int)"));
+    }
+
+    #[test]
+    fn test_undefined_field_type() {
+        #[allow(dead_code)]
+        struct Avatar;
+
+        impl ElmExport for Avatar {}
+
+        #[allow(dead_code)]
+        #[derive(ElmExport)]
+        struct Profile {
+            avatar: Avatar,
+        }
+
+        let mut definitions = ElmDefinitions::default();
+        definitions.export::<Profile>();
+        let error = definitions.into_elm_file("Profile").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Type Avatar is referenced by Profile but not defined in the input"
+        );
+    }
+
+    #[test]
+    fn test_same_name_in_different_modules() {
+        mod first {
+            #[allow(dead_code)]
+            #[derive(crate::ElmExport)]
+            pub struct Item {
+                pub a: u8,
+            }
+        }
+
+        mod second {
+            #[allow(dead_code)]
+            #[derive(crate::ElmExport)]
+            pub struct Item {
+                pub b: String,
+            }
+        }
+
+        #[allow(dead_code)]
+        #[derive(ElmExport)]
+        struct Pair {
+            first: first::Item,
+            second: second::Item,
+        }
+
+        let mut definitions = ElmDefinitions::default();
+        definitions.export::<Pair>();
+        let error = definitions.into_elm_file("Pair").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Types rust_elm_typegen::tests::derive::test::first::Item and \
+            rust_elm_typegen::tests::derive::test::second::Item would both be called Item in elm"
+        );
+    }
+
+    #[test]
+    fn test_serialize_as_expected() {
+        let login = Login {
+            user: "ada".to_string(),
+            token: SessionToken("secret".to_string()),
+        };
+        let serialized = serde_json::to_string(&login).unwrap();
+        assert_eq!(serialized, r#"{"user":"ada","token":"secret"}"#);
+    }
+}
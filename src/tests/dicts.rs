@@ -0,0 +1,35 @@
+use std::collections::{BTreeMap, HashMap};
+
+use super::ElmExport;
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Inventory {
+    stock: HashMap<String, u32>,
+    prices: BTreeMap<String, f64>,
+    tags: HashMap<String, Vec<String>>,
+}
+
+impl ElmExport for Inventory {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::Inventory;
+        let inventory = Inventory {
+            stock: [("apple".to_string(), 3)].into_iter().collect(),
+            prices: [("apple".to_string(), 0.5), ("pear".to_string(), 0.75)]
+                .into_iter()
+                .collect(),
+            tags: [("apple".to_string(), vec!["fruit".to_string()])]
+                .into_iter()
+                .collect(),
+        };
+        let serialized = serde_json::to_string(&inventory).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"stock":{"apple":3},"prices":{"apple":0.5,"pear":0.75},"tags":{"apple":["fruit"]}}"#
+        );
+    }
+}
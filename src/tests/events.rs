@@ -0,0 +1,48 @@
+use super::ElmExport;
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+enum Event {
+    Started,
+    Moved {
+        x: i32,
+        y: i32,
+    },
+    Renamed {
+        old_name: String,
+        new_name: Option<String>,
+    },
+    Scaled(f64),
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Log {
+    events: Vec<Event>,
+}
+
+impl ElmExport for Log {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::{Event, Log};
+        let log = Log {
+            events: vec![
+                Event::Started,
+                Event::Moved { x: 1, y: 2 },
+                Event::Renamed {
+                    old_name: "a".to_string(),
+                    new_name: None,
+                },
+                Event::Scaled(0.5),
+            ],
+        };
+        let serialized = serde_json::to_string(&log).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"events":["Started",{"Moved":{"x":1,"y":2}},{"Renamed":{"old_name":"a","new_name":null}},{"Scaled":0.5}]}"#
+        );
+    }
+}
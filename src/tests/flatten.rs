@@ -0,0 +1,85 @@
+use super::ElmExport;
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Meta {
+    created_by: String,
+    version: u32,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Audit {
+    #[serde(flatten)]
+    meta: Meta,
+    reviewed: bool,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Document {
+    id: u64,
+    #[serde(flatten)]
+    audit: Audit,
+    #[serde(flatten)]
+    extra: HashMap<String, String>,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+#[serde(tag = "kind")]
+enum Change {
+    Created {
+        #[serde(flatten)]
+        meta: Meta,
+    },
+    Tagged {
+        tag: String,
+        #[serde(flatten)]
+        labels: HashMap<String, u32>,
+    },
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct History {
+    document: Document,
+    changes: Vec<Change>,
+}
+
+impl ElmExport for History {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::{Audit, Change, Document, History, Meta};
+        let meta = || Meta {
+            created_by: "ada".to_string(),
+            version: 2,
+        };
+        let history = History {
+            document: Document {
+                id: 7,
+                audit: Audit {
+                    meta: meta(),
+                    reviewed: true,
+                },
+                extra: [("color".to_string(), "red".to_string())].into(),
+            },
+            changes: vec![
+                Change::Created { meta: meta() },
+                Change::Tagged {
+                    tag: "draft".to_string(),
+                    labels: [("priority".to_string(), 1)].into(),
+                },
+            ],
+        };
+        let serialized = serde_json::to_string(&history).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"document":{"id":7,"created_by":"ada","version":2,"reviewed":true,"color":"red"},"changes":[{"kind":"Created","created_by":"ada","version":2},{"kind":"Tagged","tag":"draft","priority":1}]}"#
+        );
+    }
+}
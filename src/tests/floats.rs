@@ -0,0 +1,41 @@
+use super::ElmExport;
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Measurement {
+    value: f64,
+    error: f32,
+    calibration: Option<f64>,
+}
+
+impl ElmExport for Measurement {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::Measurement;
+        let measurement = Measurement {
+            value: 1.5,
+            error: 0.25,
+            calibration: Some(2.0),
+        };
+        let serialized = serde_json::to_string(&measurement).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"value":1.5,"error":0.25,"calibration":2.0}"#
+        );
+
+        // serde_json has no representation for NaN and infinity.
+        let measurement = Measurement {
+            value: f64::NAN,
+            error: f32::INFINITY,
+            calibration: None,
+        };
+        let serialized = serde_json::to_string(&measurement).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"value":null,"error":null,"calibration":null}"#
+        );
+    }
+}
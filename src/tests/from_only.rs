@@ -0,0 +1,36 @@
+use super::ElmExport;
+use serde::{Deserialize, Serialize};
+
+/// Read from a plain number, but written as its fields.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(from = "f64")]
+struct Celsius {
+    degrees: f64,
+}
+
+impl From<f64> for Celsius {
+    fn from(degrees: f64) -> Celsius {
+        Celsius { degrees }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Forecast {
+    high: Celsius,
+}
+
+impl ElmExport for Forecast {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::{Celsius, Forecast};
+        let forecast = Forecast {
+            high: Celsius { degrees: 21.5 },
+        };
+        let serialized = serde_json::to_string(&forecast).unwrap();
+        assert_eq!(serialized, r#"{"high":{"degrees":21.5}}"#);
+        let deserialized: Forecast = serde_json::from_str(r#"{"high":21.5}"#).unwrap();
+        assert_eq!(deserialized, forecast);
+    }
+}
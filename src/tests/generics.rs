@@ -0,0 +1,67 @@
+use super::ElmExport;
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Page<T> {
+    items: Vec<T>,
+    total: u64,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Card {
+    suit: String,
+    value: u8,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Labelled<'a, K, V> {
+    label: &'a str,
+    entries: HashMap<K, V>,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+enum Update<T> {
+    Replace(T),
+    Clear,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Deck<'a> {
+    page: Page<Card>,
+    history: Vec<Update<Card>>,
+    counts: Labelled<'a, String, Option<u32>>,
+}
+
+impl ElmExport for Deck<'_> {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::{Card, Deck, Labelled, Page, Update};
+        let deck = Deck {
+            page: Page {
+                items: vec![Card {
+                    suit: "Hearts".to_string(),
+                    value: 7,
+                }],
+                total: 1,
+            },
+            history: vec![Update::Clear],
+            counts: Labelled {
+                label: "counts",
+                entries: Default::default(),
+            },
+        };
+        let serialized = serde_json::to_string(&deck).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"page":{"items":[{"suit":"Hearts","value":7}],"total":1},"history":["Clear"],"counts":{"label":"counts","entries":{}}}"#
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use super::ElmExport;
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+#[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq)]
+#[repr(u8)]
+enum Weekday {
+    Monday = 1,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+}
+
+#[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq)]
+#[repr(i16)]
+enum Offset {
+    Behind = -60,
+    OnTime = 0,
+    Ahead = 60,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Meeting {
+    day: Weekday,
+    offset: Offset,
+}
+
+impl ElmExport for Meeting {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::{Meeting, Offset, Weekday};
+        let meeting = Meeting {
+            day: Weekday::Wednesday,
+            offset: Offset::Behind,
+        };
+        let serialized = serde_json::to_string(&meeting).unwrap();
+        assert_eq!(serialized, r#"{"day":3,"offset":-60}"#);
+        let deserialized: Meeting = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, meeting);
+    }
+}
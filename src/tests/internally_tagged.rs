@@ -0,0 +1,43 @@
+use super::ElmExport;
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Chat {
+    room: String,
+    text: String,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    Ping,
+    Joined { user_name: String, at: u64 },
+    Said(Chat),
+}
+
+impl ElmExport for ServerMessage {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::{Chat, ServerMessage};
+        let messages = vec![
+            ServerMessage::Ping,
+            ServerMessage::Joined {
+                user_name: "me".to_string(),
+                at: 3,
+            },
+            ServerMessage::Said(Chat {
+                room: "main".to_string(),
+                text: "hi".to_string(),
+            }),
+        ];
+        let serialized = serde_json::to_string(&messages).unwrap();
+        assert_eq!(
+            serialized,
+            r#"[{"type":"ping"},{"type":"joined","user_name":"me","at":3},{"type":"said","room":"main","text":"hi"}]"#
+        );
+    }
+}
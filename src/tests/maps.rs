@@ -0,0 +1,50 @@
+use std::collections::{BTreeMap, HashMap};
+
+use super::ElmExport;
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Color {
+    Red,
+    Green,
+}
+
+/// Written like the number it wraps, also as a map key.
+#[allow(dead_code)]
+#[derive(Serialize, PartialEq, Eq, Hash)]
+struct UserId(u64);
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Palette {
+    names: BTreeMap<u32, String>,
+    weights: HashMap<i64, f64>,
+    usage: BTreeMap<Color, u32>,
+    enabled: BTreeMap<bool, String>,
+    owners: HashMap<UserId, String>,
+}
+
+impl ElmExport for Palette {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::Color::*;
+        use super::{Palette, UserId};
+        let palette = Palette {
+            names: [(1, "one".to_string()), (20, "twenty".to_string())]
+                .into_iter()
+                .collect(),
+            weights: [(-3, 0.5)].into_iter().collect(),
+            usage: [(Red, 3), (Green, 4)].into_iter().collect(),
+            enabled: [(true, "yes".to_string())].into_iter().collect(),
+            owners: [(UserId(7), "Ada".to_string())].into_iter().collect(),
+        };
+        let serialized = serde_json::to_string(&palette).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"names":{"1":"one","20":"twenty"},"weights":{"-3":0.5},"usage":{"Red":3,"Green":4},"enabled":{"true":"yes"},"owners":{"7":"Ada"}}"#
+        );
+    }
+}
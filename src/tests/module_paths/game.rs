@@ -0,0 +1,22 @@
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+pub struct Card {
+    pub suit: self::Suit,
+    pub rank: u8,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+pub enum Suit {
+    Hearts,
+    Spades,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+pub struct Player {
+    pub name: std::string::String,
+    pub favorite: Option<super::shop::Card>,
+}
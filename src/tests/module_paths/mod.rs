@@ -0,0 +1,50 @@
+//! Read with `RustFile::parse_crate`. Types are named by their paths, and
+//! both `game` and `shop` define a `Card`.
+use crate::ElmExport;
+use serde::Serialize;
+
+mod game;
+mod shop;
+
+use game::Player;
+use shop::Card as Offer;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Table {
+    players: Vec<Player>,
+    hand: Vec<game::Card>,
+    offers: Vec<Offer>,
+    scores: std::collections::BTreeMap<String, u32>,
+}
+
+impl ElmExport for Table {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::game::{Card, Player, Suit};
+        use super::shop;
+        use super::Table;
+        let table = Table {
+            players: vec![Player {
+                name: "Ada".to_string(),
+                favorite: None,
+            }],
+            hand: vec![Card {
+                suit: Suit::Hearts,
+                rank: 7,
+            }],
+            offers: vec![shop::Card {
+                price: 3,
+                suit: Suit::Spades,
+            }],
+            scores: [("Ada".to_string(), 12)].into_iter().collect(),
+        };
+        let serialized = serde_json::to_string(&table).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"players":[{"name":"Ada","favorite":null}],"hand":[{"suit":"Hearts","rank":7}],"offers":[{"price":3,"suit":"Spades"}],"scores":{"Ada":12}}"#
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+use super::game::*;
+
+/// Not the `Card` from the glob import.
+#[allow(dead_code)]
+#[derive(Serialize)]
+pub struct Card {
+    pub price: u32,
+    pub suit: Suit,
+}
@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+pub mod cards;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+pub struct Player {
+    pub name: String,
+    pub hand: Vec<cards::Card>,
+    pub discarded: cards::Pile<cards::Card>,
+}
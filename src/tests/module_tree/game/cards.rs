@@ -0,0 +1,21 @@
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+pub struct Card {
+    pub suit: Suit,
+    pub rank: u8,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+pub enum Suit {
+    Hearts,
+    Spades,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+pub struct Pile<T> {
+    pub cards: Vec<T>,
+}
@@ -0,0 +1,52 @@
+//! Read with `RustFile::parse_crate` and split into one elm module for each
+//! rust module.
+use crate::ElmExport;
+use serde::Serialize;
+
+mod game;
+mod shop;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Table {
+    players: Vec<game::Player>,
+    offers: Vec<shop::Offer>,
+}
+
+impl ElmExport for Table {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::game::cards::{Card, Pile, Suit};
+        use super::game::Player;
+        use super::shop::{self, Offer};
+        use super::Table;
+        let card = || Card {
+            suit: Suit::Hearts,
+            rank: 7,
+        };
+        let table = Table {
+            players: vec![Player {
+                name: "Ada".to_string(),
+                hand: vec![card()],
+                discarded: Pile { cards: vec![] },
+            }],
+            offers: vec![
+                Offer::Single(shop::Card {
+                    price: 3,
+                    card: card(),
+                }),
+                Offer::Bundle {
+                    cards: vec![card()],
+                    price: 5,
+                },
+            ],
+        };
+        let serialized = serde_json::to_string(&table).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"players":[{"name":"Ada","hand":[{"suit":"Hearts","rank":7}],"discarded":{"cards":[]}}],"offers":[{"Single":{"price":3,"card":{"suit":"Hearts","rank":7}}},{"Bundle":{"cards":[{"suit":"Hearts","rank":7}],"price":5}}]}"#
+        );
+    }
+}
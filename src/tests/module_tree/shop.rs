@@ -0,0 +1,18 @@
+use serde::Serialize;
+
+use super::game::cards;
+
+/// Has the same name as `cards::Card`, but lives in another elm module.
+#[allow(dead_code)]
+#[derive(Serialize)]
+pub struct Card {
+    pub price: u32,
+    pub card: cards::Card,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+pub enum Offer {
+    Single(Card),
+    Bundle { cards: Vec<cards::Card>, price: u32 },
+}
@@ -0,0 +1,57 @@
+use super::ElmExport;
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct UserId(u64);
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Email(String);
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Point(i32, i32, Option<String>);
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Marker;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Tagged<T>(T);
+
+// Not exported, but must not stop the other types from being generated.
+#[allow(dead_code)]
+struct Internal(u8, u8);
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Account {
+    id: UserId,
+    email: Email,
+    position: Point,
+    marker: Marker,
+    friends: Vec<Tagged<UserId>>,
+}
+
+impl ElmExport for Account {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::{Account, Email, Marker, Point, Tagged, UserId};
+        let account = Account {
+            id: UserId(7),
+            email: Email("me@example.com".to_string()),
+            position: Point(1, -2, None),
+            marker: Marker,
+            friends: vec![Tagged(UserId(3))],
+        };
+        let serialized = serde_json::to_string(&account).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"id":7,"email":"me@example.com","position":[1,-2,null],"marker":null,"friends":[3]}"#
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use super::ElmExport;
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[allow(dead_code)]
+#[derive(Serialize, Default)]
+struct Theme {
+    dark: bool,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Settings {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nickname: Option<String>,
+    #[serde(default)]
+    volume: u8,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default)]
+    limits: HashMap<u32, String>,
+    #[serde(default)]
+    theme: Theme,
+    #[serde(skip)]
+    cache: Vec<u8>,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize, Default)]
+#[serde(default)]
+struct Window {
+    width: u32,
+    title: Option<String>,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Preferences {
+    settings: Settings,
+    window: Window,
+}
+
+impl ElmExport for Preferences {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::{Preferences, Settings, Window};
+        let preferences = Preferences {
+            settings: Settings {
+                name: "me".to_string(),
+                nickname: None,
+                volume: 3,
+                tags: vec![],
+                limits: Default::default(),
+                theme: Default::default(),
+                cache: vec![1, 2],
+            },
+            window: Window::default(),
+        };
+        let serialized = serde_json::to_string(&preferences).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"settings":{"name":"me","volume":3,"limits":{},"theme":{"dark":false}},"window":{"width":0,"title":null}}"#
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::ElmExport;
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Shared<'a> {
+    boxed: Box<u32>,
+    counted: Rc<String>,
+    atomic: Arc<Vec<bool>>,
+    borrowed: &'a str,
+    cow: Cow<'a, str>,
+    boxed_str: Box<str>,
+    cow_slice: Cow<'a, [u8]>,
+}
+
+impl<'a> ElmExport for Shared<'a> {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::Shared;
+        use std::borrow::Cow;
+        let shared = Shared {
+            boxed: Box::new(1),
+            counted: "counted".to_string().into(),
+            atomic: vec![true].into(),
+            borrowed: "borrowed",
+            cow: Cow::Borrowed("cow"),
+            boxed_str: "boxed".into(),
+            cow_slice: Cow::Owned(vec![2, 3]),
+        };
+        let serialized = serde_json::to_string(&shared).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"boxed":1,"counted":"counted","atomic":[true],"borrowed":"borrowed","cow":"cow","boxed_str":"boxed","cow_slice":[2,3]}"#
+        );
+    }
+}
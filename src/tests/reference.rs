@@ -19,5 +19,4 @@ enum MixedData {
 }
 
 impl ElmExport for WeatherData {}
-impl ElmExport for Coordinate {}
 impl ElmExport for MixedData {}
@@ -0,0 +1,66 @@
+use super::ElmExport;
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Profile {
+    display_name: String,
+    #[serde(rename = "e-mail")]
+    email_address: String,
+    status: Status,
+    last_action: Action,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Status {
+    OnlineNow,
+    #[serde(rename = "away")]
+    Away,
+    Busy(String),
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case", rename_all_fields = "PascalCase")]
+enum Action {
+    LoggedIn {
+        user_name: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    LoggedOut {
+        user_name: String,
+    },
+}
+
+impl ElmExport for Profile {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::{Action, Profile, Status};
+        let profile = Profile {
+            display_name: "Me".to_string(),
+            email_address: "me@example.com".to_string(),
+            status: Status::OnlineNow,
+            last_action: Action::LoggedIn {
+                user_name: "me".to_string(),
+            },
+        };
+        let serialized = serde_json::to_string(&profile).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"displayName":"Me","e-mail":"me@example.com","status":"ONLINE_NOW","lastAction":{"logged-in":{"UserName":"me"}}}"#
+        );
+
+        let serialized = serde_json::to_string(&vec![Status::Away, Status::Busy("x".into())]);
+        assert_eq!(serialized.unwrap(), r#"["away",{"BUSY":"x"}]"#);
+
+        let serialized = serde_json::to_string(&Action::LoggedOut {
+            user_name: "me".to_string(),
+        });
+        assert_eq!(serialized.unwrap(), r#"{"logged-out":{"userName":"me"}}"#);
+    }
+}
@@ -0,0 +1,47 @@
+use super::ElmExport;
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Payload {
+    value: u32,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+enum ApiError {
+    NotFound,
+    Invalid(String),
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Response {
+    result: Result<Payload, ApiError>,
+    checks: Vec<Result<(), String>>,
+}
+
+impl ElmExport for Response {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::{ApiError, Payload, Response};
+        let response = Response {
+            result: Ok(Payload { value: 1 }),
+            checks: vec![Ok(()), Err("failed".to_string())],
+        };
+        let serialized = serde_json::to_string(&response).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"result":{"Ok":{"value":1}},"checks":[{"Ok":null},{"Err":"failed"}]}"#
+        );
+
+        let response = Response {
+            result: Err(ApiError::NotFound),
+            checks: vec![],
+        };
+        let serialized = serde_json::to_string(&response).unwrap();
+        assert_eq!(serialized, r#"{"result":{"Err":"NotFound"},"checks":[]}"#);
+    }
+}
@@ -0,0 +1,48 @@
+use std::collections::{BTreeSet, HashSet, LinkedList, VecDeque};
+
+use super::ElmExport;
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Collections {
+    fixed: [u8; 3],
+    boxed: Box<[String]>,
+    queue: VecDeque<u32>,
+    linked: LinkedList<bool>,
+    ordered: BTreeSet<i32>,
+    unique: HashSet<String>,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Borrowed<'a> {
+    values: &'a [u32],
+}
+
+impl ElmExport for Collections {}
+impl ElmExport for Borrowed<'_> {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::{Borrowed, Collections};
+        let collections = Collections {
+            fixed: [1, 2, 3],
+            boxed: vec!["a".to_string()].into_boxed_slice(),
+            queue: [4, 5].into_iter().collect(),
+            linked: [true].into_iter().collect(),
+            ordered: [3, 1, 2].into_iter().collect(),
+            unique: ["b".to_string()].into_iter().collect(),
+        };
+        let serialized = serde_json::to_string(&collections).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"fixed":[1,2,3],"boxed":["a"],"queue":[4,5],"linked":[true],"ordered":[1,2,3],"unique":["b"]}"#
+        );
+
+        let borrowed = Borrowed { values: &[6, 7] };
+        let serialized = serde_json::to_string(&borrowed).unwrap();
+        assert_eq!(serialized, r#"{"values":[6,7]}"#);
+    }
+}
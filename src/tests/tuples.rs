@@ -0,0 +1,33 @@
+use super::ElmExport;
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Shapes {
+    pair: (u32, String),
+    triple: (bool, f64, String),
+    quad: (u8, u8, u8, u8),
+    points: Vec<(i32, i32)>,
+    nothing: (),
+}
+
+impl ElmExport for Shapes {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::Shapes;
+        let shapes = Shapes {
+            pair: (1, "one".to_string()),
+            triple: (true, 0.5, "half".to_string()),
+            quad: (1, 2, 3, 4),
+            points: vec![(0, 1), (2, 3)],
+            nothing: (),
+        };
+        let serialized = serde_json::to_string(&shapes).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"pair":[1,"one"],"triple":[true,0.5,"half"],"quad":[1,2,3,4],"points":[[0,1],[2,3]],"nothing":null}"#
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use super::ElmExport;
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Named {
+    name: String,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Value {
+    Missing,
+    Count(u32),
+    Ratio(f64),
+    // Never decoded: Count already accepts the same numbers.
+    Small(u8),
+    Label(String),
+    Range(u32, u32),
+    Person(Named),
+    // Never decoded: Person already accepts objects with a name.
+    Employee { name: String, team: String },
+}
+
+impl ElmExport for Value {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::{Named, Value};
+        let values = vec![
+            Value::Missing,
+            Value::Count(3),
+            Value::Ratio(0.5),
+            Value::Small(1),
+            Value::Label("x".to_string()),
+            Value::Range(1, 2),
+            Value::Person(Named {
+                name: "me".to_string(),
+            }),
+            Value::Employee {
+                name: "me".to_string(),
+                team: "core".to_string(),
+            },
+        ];
+        let serialized = serde_json::to_string(&values).unwrap();
+        assert_eq!(
+            serialized,
+            r#"[null,3,0.5,1,"x",[1,2],{"name":"me"},{"name":"me","team":"core"}]"#
+        );
+    }
+}
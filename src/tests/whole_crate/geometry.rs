@@ -0,0 +1,12 @@
+use serde::Serialize;
+
+pub mod point;
+
+use point::Point;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+pub struct Line {
+    pub from: Point,
+    pub to: Point,
+}
@@ -0,0 +1,8 @@
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
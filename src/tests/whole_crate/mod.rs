@@ -0,0 +1,57 @@
+//! Read with `RustFile::parse_crate`, starting at this file.
+use crate::ElmExport;
+use serde::Serialize;
+
+#[path = "shared/palette.rs"]
+mod colors;
+mod geometry;
+
+mod drawing {
+    use super::colors::Color;
+    use super::geometry::Line;
+    use crate::ElmExport;
+    use serde::Serialize;
+
+    #[allow(dead_code)]
+    #[derive(Serialize)]
+    pub struct Drawing {
+        pub title: String,
+        pub lines: Vec<Line>,
+        pub color: Color,
+    }
+
+    impl ElmExport for Drawing {}
+}
+
+use drawing::Drawing;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+struct Gallery {
+    drawings: Vec<Drawing>,
+}
+
+impl ElmExport for Gallery {}
+
+mod test {
+    #[test]
+    fn test_serialize_as_expected() {
+        use super::colors::Color;
+        use super::drawing::Drawing;
+        use super::geometry::point::Point;
+        use super::geometry::Line;
+        let drawing = Drawing {
+            title: "Sketch".to_string(),
+            lines: vec![Line {
+                from: Point { x: 0.0, y: 0.0 },
+                to: Point { x: 1.0, y: 2.0 },
+            }],
+            color: Color::Custom("#123456".to_string()),
+        };
+        let serialized = serde_json::to_string(&drawing).unwrap();
+        assert_eq!(
+            serialized,
+            r##"{"title":"Sketch","lines":[{"from":{"x":0.0,"y":0.0},"to":{"x":1.0,"y":2.0}}],"color":{"Custom":"#123456"}}"##
+        );
+    }
+}
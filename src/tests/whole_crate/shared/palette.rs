@@ -0,0 +1,8 @@
+use serde::Serialize;
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+pub enum Color {
+    Black,
+    Custom(String),
+}